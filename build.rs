@@ -0,0 +1,48 @@
+//! Generates a regression test for every input of every day with answers stored
+//! in its `answers.txt`, see `src/regression`.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Days whose tests can't pass in debug builds yet, with the reason
+const IGNORED: &[(u32, &str)] = &[];
+
+fn main() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut days: Vec<(u32, String)> = fs::read_dir(&src)
+        .expect("Could not read src")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let day = name.strip_prefix("day_")?.parse().ok()?;
+            Some((day, name))
+        })
+        .collect();
+    days.sort();
+
+    let mut tests = String::new();
+    for (day, dir) in days {
+        let Ok(answers) = fs::read_to_string(src.join(&dir).join("answers.txt")) else { continue };
+        // Keys look like `examples/small.part1`, one test covers both parts of an input
+        let inputs: BTreeSet<&str> = answers
+            .lines()
+            .filter_map(|line| line.split_once(" = "))
+            .filter_map(|(key, _)| key.trim().rsplit_once(".part").map(|(input, _)| input))
+            .collect();
+
+        for input in inputs {
+            let ignore = match IGNORED.iter().find(|(ignored, _)| *ignored == day) {
+                Some((_, reason)) => format!("#[ignore = {:?}] ", reason),
+                None => String::new(),
+            };
+            let name: String = input.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+            tests.push_str(&format!("regression_test!({}{}_{}, {}, {:?});\n", ignore, dir, name, day, input));
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("regression.rs");
+    fs::write(out, tests).expect("Could not write the regression tests");
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// Result of a single puzzle part.
+///
+/// Most puzzles are answered with a number, but some want a string of letters
+/// (day 13) or a model number (day 24). Multi-line grids are kept row by row so
+/// the runner can print them below the part label instead of inline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+    Grid(Vec<String>),
+    /// Part has no puzzle to solve (e.g. the second half of day 25).
+    Empty,
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::BigInt(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::Empty => write!(f, "-"),
+        }
+    }
+}
+
+// Integers that don't fit into an i64 fall back to the big integer variant
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(value) => Answer::Int(value),
+                        Err(_) => Answer::BigInt(value as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Int(value),
+            Err(_) => Answer::BigInt(value),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Answer::Grid(rows)
+    }
+}
@@ -0,0 +1,154 @@
+use std::time::Duration;
+use owo_colors::OwoColorize;
+use humantime::format_duration;
+use crate::guard::{self, Outcome};
+use advent_of_code::params::Params;
+use crate::{get_time, Constructor};
+
+/// Summary of the timings of repeated runs.
+pub struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(times: &[Duration]) -> Self {
+        let mut sorted = times.to_vec();
+        sorted.sort_unstable();
+
+        let len = sorted.len();
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+
+        let mean_nanos = sorted.iter().map(|t| t.as_nanos() as f64).sum::<f64>() / len as f64;
+        let variance = sorted
+            .iter()
+            .map(|t| (t.as_nanos() as f64 - mean_nanos).powi(2))
+            .sum::<f64>() / len as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean_nanos.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+
+    fn print_row(&self, label: &str) {
+        let cell = |d: Duration| format!("{:>20}", format_duration(d).to_string());
+        println!(
+            "{:<6} | {} | {} | {} | {}",
+            label,
+            cell(self.min).fg_rgb::<255, 63, 128>(),
+            cell(self.median).fg_rgb::<255, 63, 128>(),
+            cell(self.mean).fg_rgb::<255, 63, 128>(),
+            cell(self.stddev).fg_rgb::<255, 63, 128>(),
+        );
+    }
+}
+
+/// Timings of every measured run of a single day.
+pub struct Bench {
+    new: Vec<Duration>,
+    part1: Vec<Duration>,
+    part2: Vec<Duration>,
+}
+
+impl Bench {
+    /// Runs the day `warmup + runs` times on fresh instances, keeping only the last `runs` timings.
+    ///
+    /// Stops at the first part which panics or times out, returning it with its outcome.
+    pub fn run(
+        day: u32,
+        constructor: Constructor,
+        content: &str,
+        params: &Params,
+        runs: usize,
+        warmup: usize,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<Result<Self, (u8, Outcome)>> {
+        let mut bench = Bench { new: vec![], part1: vec![], part2: vec![] };
+        for i in 0..warmup + runs {
+            let (event, time) = get_time(|| constructor(content));
+            let mut event = event?;
+            params.apply(event.as_mut())?;
+            let mut times = [Duration::ZERO; 2];
+            for part in 1..=2 {
+                let (solved, result) = guard::run(event, day, part, timeout);
+                match solved {
+                    Some(solved) if result.outcome.is_ok() => event = solved,
+                    _ => return Ok(Err((part, result.outcome))),
+                }
+                times[part as usize - 1] = result.time;
+            }
+
+            if i >= warmup {
+                bench.new.push(time);
+                bench.part1.push(times[0]);
+                bench.part2.push(times[1]);
+            }
+        }
+        Ok(Ok(bench))
+    }
+
+    /// Total duration of each run.
+    pub fn totals(&self) -> Vec<Duration> {
+        self.new.iter().zip(&self.part1).zip(&self.part2)
+            .map(|((time, time1), time2)| *time + *time1 + *time2)
+            .collect()
+    }
+
+    pub fn print(&self, day: u32) {
+        println!("-----------------------------");
+        println!("Benchmark for day {} ({} runs)", day, self.new.len());
+        print_header();
+        Stats::new(&self.new).print_row("new");
+        Stats::new(&self.part1).print_row("part 1");
+        Stats::new(&self.part2).print_row("part 2");
+        Stats::new(&self.totals()).print_row("total");
+    }
+}
+
+pub fn print_header() {
+    println!(
+        "{:<6} | {:>20} | {:>20} | {:>20} | {:>20}",
+        "Phase", "min", "median", "mean", "stddev"
+    );
+}
+
+/// Prints the statistics of all selected days run together.
+pub fn print_total(totals: &[Duration]) {
+    println!("-----------------------------");
+    println!("Benchmark total ({} runs)", totals.len());
+    print_header();
+    Stats::new(totals).print_row("total");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_odd_runs() {
+        let times: Vec<Duration> = [3, 1, 2].iter().map(|n| Duration::from_nanos(*n)).collect();
+        let stats = Stats::new(&times);
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(2));
+        assert_eq!(stats.mean, Duration::from_nanos(2));
+        assert_eq!(stats.stddev, Duration::from_nanos(1));
+    }
+
+    #[test]
+    fn stats_of_even_runs() {
+        let times: Vec<Duration> = [4, 1, 2, 9].iter().map(|n| Duration::from_nanos(*n)).collect();
+        let stats = Stats::new(&times);
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.mean, Duration::from_nanos(4));
+        assert_eq!(stats.stddev, Duration::from_nanos(3));
+    }
+}
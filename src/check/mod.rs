@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Context;
+use owo_colors::OwoColorize;
+use advent_of_code::answer::Answer;
+use crate::report::Record;
+
+/// Known answers of a single day, stored in `answers.txt` next to its inputs.
+///
+/// Each line holds one answer, keyed by the input file and the part, e.g.:
+///
+/// ```text
+/// input.part1 = 1139
+/// example.part2 = 5
+/// ```
+///
+/// Multi-line answers are written on a single line with escaped newlines.
+pub struct ExpectedAnswers {
+    path: PathBuf,
+    answers: BTreeMap<String, String>,
+}
+
+impl ExpectedAnswers {
+    pub fn load(day_dir: &Path) -> anyhow::Result<Self> {
+        let path = day_dir.join("answers.txt");
+        let mut answers = BTreeMap::new();
+
+        if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            for (i, line) in content.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let (key, value) = line.split_once(" = ")
+                    .with_context(|| format!("Invalid answer on line {} of {}", i + 1, path.display()))?;
+                answers.insert(key.to_string(), value.to_string());
+            }
+        }
+
+        Ok(ExpectedAnswers { path, answers })
+    }
+
+    pub fn get(&self, main_file: &str, part: u8) -> Option<&str> {
+        self.answers.get(&key(main_file, part)).map(|v| v.as_str())
+    }
+
+    pub fn set(&mut self, main_file: &str, part: u8, answer: &Answer) {
+        self.answers.insert(key(main_file, part), escape(answer));
+    }
+
+    /// Stores the answers of the parts the record solved and returns the other parts, whose
+    /// stored answers are kept instead of being replaced by a panic or timeout.
+    pub fn record(&mut self, record: &Record, parts: impl Iterator<Item = u8>) -> Vec<u8> {
+        let mut unsolved = vec![];
+        for part in parts {
+            if record.status(part).is_ok() {
+                self.set(&record.input, part, record.answer(part));
+            } else {
+                unsolved.push(part);
+            }
+        }
+        unsolved
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let content: String = self.answers
+            .iter()
+            .map(|(key, value)| format!("{} = {}\n", key, value))
+            .collect();
+        fs::write(&self.path, content)
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+}
+
+fn key(main_file: &str, part: u8) -> String {
+    format!("{}.part{}", main_file, part)
+}
+
+/// Answers are compared in their stored form so grids line up with the file.
+pub fn escape(answer: &Answer) -> String {
+    answer.to_string().replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[derive(PartialEq)]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+/// Outcome of comparing one part against its stored answer.
+pub struct Check {
+    day: u32,
+    input: String,
+    part: u8,
+    expected: Option<String>,
+    actual: String,
+}
+
+impl Check {
+    pub fn new(day: u32, input: &str, part: u8, expected: Option<&str>, actual: &Answer) -> Self {
+        Check { day, input: input.to_string(), part, expected: expected.map(|e| e.to_string()), actual: escape(actual) }
+    }
+
+    fn status(&self) -> Status {
+        match &self.expected {
+            Some(expected) if *expected == self.actual => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Missing,
+        }
+    }
+}
+
+/// Prints the checks as a table and returns how many of them failed.
+pub fn print_table(checks: &[Check]) -> usize {
+    let width = |f: &dyn Fn(&Check) -> usize| checks.iter().map(f).max().unwrap_or(0);
+    let input_width = width(&|c| c.input.len()).max("Input".len());
+    let expected_width = width(&|c| c.expected.as_deref().unwrap_or("-").len()).max("Expected".len());
+    let actual_width = width(&|c| c.actual.len()).max("Actual".len());
+
+    println!("-----------------------------");
+    println!(
+        "{:>3} | {:<iw$} | {:>4} | {:<ew$} | {:<aw$} | Status",
+        "Day", "Input", "Part", "Expected", "Actual",
+        iw = input_width, ew = expected_width, aw = actual_width
+    );
+    for check in checks {
+        let status = match check.status() {
+            Status::Pass => format!("{}", "PASS".green()),
+            Status::Fail => format!("{}", "FAIL".red()),
+            Status::Missing => format!("{}", "MISSING".yellow()),
+        };
+        println!(
+            "{:>3} | {:<iw$} | {:>4} | {:<ew$} | {:<aw$} | {}",
+            check.day, check.input, check.part, check.expected.as_deref().unwrap_or("-"), check.actual, status,
+            iw = input_width, ew = expected_width, aw = actual_width
+        );
+    }
+
+    checks.iter().filter(|c| c.status() == Status::Fail).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::guard::Outcome;
+
+    #[test]
+    fn escape_grid() {
+        let answer = Answer::Grid(vec!["#.".to_string(), ".#".to_string()]);
+        assert_eq!(escape(&answer), "#.\\n.#");
+    }
+
+    #[test]
+    fn records_only_solved_parts() {
+        let mut expected = ExpectedAnswers { path: PathBuf::new(), answers: BTreeMap::new() };
+        expected.set("example", 2, &Answer::Int(44169));
+        let record = Record {
+            day: 23,
+            name: "Amphipod",
+            input: "example".to_string(),
+            parse_time: Duration::ZERO,
+            part1: Answer::Int(12521),
+            time1: Duration::ZERO,
+            status1: Outcome::Ok,
+            part2: Answer::Empty,
+            time2: Duration::ZERO,
+            status2: Outcome::Timeout,
+            cpu_time: Duration::ZERO,
+            memory: [None; 3],
+        };
+        assert_eq!(expected.record(&record, 1..=2), vec![2]);
+        assert_eq!(expected.get("example", 1), Some("12521"));
+        assert_eq!(expected.get("example", 2), Some("44169"));
+    }
+
+    #[test]
+    fn check_status() {
+        let answer = Answer::Int(42);
+        assert!(Check::new(1, "input", 1, Some("42"), &answer).status() == Status::Pass);
+        assert!(Check::new(1, "input", 1, Some("41"), &answer).status() == Status::Fail);
+        assert!(Check::new(1, "input", 1, None, &answer).status() == Status::Missing);
+    }
+}
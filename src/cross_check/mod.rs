@@ -0,0 +1,109 @@
+use std::time::Duration;
+use humantime::format_duration;
+use owo_colors::OwoColorize;
+use crate::check::escape;
+use advent_of_code::params::Params;
+use crate::registry::{self, Puzzle};
+use crate::report::Record;
+use crate::solution::{parts, Solution};
+
+/// Answers of one implementation of a day.
+pub struct Run {
+    pub variant: &'static str,
+    pub record: Record,
+}
+
+impl Run {
+    fn result(&self, part: u8) -> String {
+        match self.record.status(part) {
+            status if status.is_ok() => escape(self.record.answer(part)),
+            status => status.to_string(),
+        }
+    }
+
+    /// Whether both runs solved the parts with the same answers.
+    fn agrees(&self, other: &Run, selected: Option<u8>) -> bool {
+        parts(selected).all(|part| {
+            self.record.status(part).is_ok() && other.record.status(part).is_ok() && self.result(part) == other.result(part)
+        })
+    }
+}
+
+/// Solves the input with every implementation of the day, the default one first.
+pub fn run(
+    puzzle: &Puzzle,
+    input: &str,
+    content: &str,
+    params: &Params,
+    part: Option<u8>,
+    timeout: Option<Duration>,
+) -> anyhow::Result<Vec<Run>> {
+    registry::variants(puzzle)
+        .into_iter()
+        .map(|(variant, puzzle)| {
+            let mut solution = Solution::new(puzzle.constructor, content)?.with_params(params)?;
+            Ok(Run { variant, record: solution.get_result(&puzzle, input, part, timeout) })
+        })
+        .collect()
+}
+
+/// Prints the runs of a day next to each other and returns how many disagree with the default one.
+pub fn print_table(runs: &[Run], selected: Option<u8>) -> usize {
+    let Some(reference) = runs.first() else { return 0 };
+    let width = |f: &dyn Fn(&Run) -> usize, header: &str| runs.iter().map(f).max().unwrap_or(0).max(header.len());
+    let variant_width = width(&|r| r.variant.len(), "Variant");
+    let answer_widths: Vec<(u8, usize)> = parts(selected)
+        .map(|part| (part, width(&|r| r.result(part).len(), &format!("Part {}", part))))
+        .collect();
+    let time = |r: &Run| format_duration(r.record.total()).to_string();
+    let time_width = width(&|r| time(r).len(), "Time");
+
+    println!("-----------------------------");
+    let headers: Vec<String> = answer_widths.iter().map(|&(part, w)| format!("{:<w$}", format!("Part {}", part), w = w)).collect();
+    println!(
+        "{:>3} | {:<vw$} | {} | {:>tw$} | {:>8} | Status",
+        "Day", "Variant", headers.join(" | "), "Time", "Relative",
+        vw = variant_width, tw = time_width
+    );
+
+    let mut disagreeing = 0;
+    for (i, run) in runs.iter().enumerate() {
+        let answers: Vec<String> = answer_widths.iter().map(|&(part, w)| format!("{:<w$}", run.result(part), w = w)).collect();
+        // Time relative to the default implementation
+        let relative = run.record.total().as_secs_f64() / reference.record.total().as_secs_f64().max(f64::MIN_POSITIVE);
+        let status = if i == 0 {
+            String::new()
+        } else if run.agrees(reference, selected) {
+            format!("{}", "AGREE".green())
+        } else {
+            disagreeing += 1;
+            format!("{}", "DIFFER".red())
+        };
+        println!(
+            "{:>3} | {:<vw$} | {} | {:>tw$} | {:>7.2}x | {}",
+            run.record.day, run.variant, answers.join(" | "), time(run).fg_rgb::<255, 63, 128>(), relative, status,
+            vw = variant_width, tw = time_width
+        );
+    }
+    disagreeing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::{day_dir, read_input};
+    use std::path::Path;
+
+    #[test]
+    fn variants_agree_on_examples() {
+        let src = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+        for puzzle in registry::PUZZLES.iter().filter(|p| registry::variants(p).len() > 1) {
+            let content = read_input(None, &day_dir(src, puzzle.day), "example").unwrap();
+            let runs = run(puzzle, "example", content.trim(), &Params::default(), None, None).unwrap();
+            assert_eq!(runs[0].variant, registry::DEFAULT_VARIANT);
+            for other in &runs[1..] {
+                assert!(other.agrees(&runs[0], None), "{} of day {}", other.variant, puzzle.day);
+            }
+        }
+    }
+}
@@ -1,31 +1,34 @@
 use std::collections::VecDeque;
 
+/// Owned child of a [`Node`].
 pub type NodePtr = Box<Node>;
 
-#[derive(Debug)]
-#[allow(dead_code)]
-struct Tree {
+/// Binary search tree of distinct integers, inserting a value twice keeps one.
+#[derive(Debug, Default)]
+pub struct Tree {
     root: Option<NodePtr>,
 }
 
 impl Tree {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Tree { root: None }
     }
 
-    fn level_iter(&self) -> LevelTraversal {
+    /// Values level by level from the root, left to right.
+    pub fn level_iter(&self) -> LevelTraversal<'_> {
         LevelTraversal::new(self.root.as_ref())
     }
 
-    fn inorder_iter(&self) -> InorderTraversal {
+    /// Values in ascending order.
+    pub fn inorder_iter(&self) -> InorderTraversal<'_> {
         InorderTraversal::new(self.root.as_ref())
     }
 
-    fn insert(&mut self, value: i32) -> () {
+    pub fn insert(&mut self, value: i32) {
         self.insert_iterative(value);
     }
 
-    fn insert_iterative(&mut self, value: i32) {
+    pub fn insert_iterative(&mut self, value: i32) {
         if self.root.is_none() {
             self.root = Node::new(value).into();
             return;
@@ -61,7 +64,8 @@ impl Tree {
 
     }
 
-    fn insert_recursive(node: &mut NodePtr, value: i32) {
+    /// Inserts below `node`, same as [`Tree::insert_iterative`] but recursing down the tree.
+    pub fn insert_recursive(node: &mut NodePtr, value: i32) {
         if value > node.value {
             match &mut node.right {
                 None => {
@@ -85,7 +89,8 @@ impl Tree {
 }
 
 
-struct LevelTraversal<'a> {
+/// Iterator of [`Tree::level_iter`].
+pub struct LevelTraversal<'a> {
     current: Option<&'a NodePtr>,
     queue: VecDeque<&'a NodePtr>
 }
@@ -120,7 +125,8 @@ impl<'a> Iterator for LevelTraversal<'a> {
     }
 }
 
-struct InorderTraversal<'a> {
+/// Iterator of [`Tree::inorder_iter`].
+pub struct InorderTraversal<'a> {
     current: Option<&'a NodePtr>,
     queue: Vec<&'a NodePtr>,
 }
@@ -153,16 +159,15 @@ impl<'a> Iterator for InorderTraversal<'a> {
 }
 
 
-#[allow(dead_code)]
 #[derive(Debug)]
-struct Node {
-    value: i32,
+pub struct Node {
+    pub value: i32,
     left: Option<NodePtr>,
     right: Option<NodePtr>
 }
 
 impl Node {
-    fn new(value: i32) -> Self {
+    pub fn new(value: i32) -> Self {
         Node {
             value,
             left: None,
@@ -216,7 +221,7 @@ mod tests {
         tree.insert(6);
         tree.insert(4);
         println!("Tree: {:?}", tree);
-        assert_eq!(tree.root.is_some(), true);        
+        assert!(tree.root.is_some());        
     }
 
     #[test]
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
+/// Element of a [`List`], holding a weak link back so the list doesn't leak.
 pub struct Node<T: Copy> {
     pub value: T,
     pub next: Option<Rc<RefCell<Node<T>>>>,
@@ -26,12 +27,18 @@ impl<T: Copy> From<Node<T>> for Option<Rc<RefCell<Node<T>>>> {
 type NodePtr<T> = Rc<RefCell<Node<T>>>;
 
 
+/// Doubly linked list with pushes and pops on both ends.
 pub struct List<T: Copy> {
     head: Option<NodePtr<T>>,
     tail: Option<NodePtr<T>>
 }
 
-#[allow(dead_code)]
+impl<T: Copy> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Copy> List<T> {
     pub fn new() -> Self {
         List {
@@ -49,7 +56,7 @@ impl<T: Copy> List<T> {
                 self.tail = self.head.clone();
             },
             Some(current_tail) => {
-                node.prev = Some(Rc::downgrade(&current_tail));
+                node.prev = Some(Rc::downgrade(current_tail));
                 self.tail = node.into();
                 current_tail.borrow_mut().next = self.tail.clone();                                
             }
@@ -94,7 +101,7 @@ impl<T: Copy> List<T> {
                 self.head = node.into();
                 if let Some(h) = &self.head {
                     current_head.borrow_mut().prev = Some(
-                        Rc::downgrade(&h)
+                        Rc::downgrade(h)
                     );                    
                 }
             }
@@ -126,7 +133,7 @@ impl<T: Copy> List<T> {
 
 impl<T: Copy> Drop for List<T> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop_back() {}
+        while self.pop_back().is_some() {}
     }
 }
 
@@ -1,3 +1,5 @@
+//! Linked structures kept around for the days which need them.
+
 pub mod double_linked_list;
 pub mod binary_tree;
-pub mod rc_binary_tree;
\ No newline at end of file
+pub mod rc_binary_tree;
@@ -1,27 +1,32 @@
+use crate::answer::Answer;
+use crate::parse::Input;
 pub struct SonarSweep {
     data: Vec<u32>,
 }
 
 impl crate::Advent for SonarSweep {
-    fn new(data: &str) -> Self {
-        SonarSweep {
-            data: data.lines().filter_map(|l| l.parse().ok()).collect()
-        }
+    fn new(data: &str) -> anyhow::Result<Self> {
+        let input = Input::new(data);
+        Ok(SonarSweep {
+            data: input.lines().map(|l| input.parse(l)).collect::<Result<_, _>>()?
+        })
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         self.data 
             .iter()
             .zip(self.data.iter().skip(1))
             .filter(|(a, b)| a < b)
             .count()
+            .into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         self.data
             .windows(3)
             .zip(self.data.windows(3).skip(1))
             .filter(|(a, b)| a.iter().sum::<u32>() < b.iter().sum())
             .count()
+            .into()
     }
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
 use std::str::FromStr;
+use crate::answer::Answer;
+use crate::parse::Input;
 
 enum Direction {
     Forward,
@@ -7,14 +9,14 @@ enum Direction {
 }
 
 impl FromStr for Direction {
-    type Err = ();
+    type Err = &'static str;
 
     fn from_str(input: &str) -> Result<Direction, Self::Err> {
         match input {
             "forward" => Ok(Direction::Forward),
             "up" => Ok(Direction::Up),
             "down" => Ok(Direction::Down),
-            _ => Err(()),
+            _ => Err("expected forward, down or up"),
         }        
     }
 }
@@ -29,21 +31,35 @@ pub struct Dive {
 }
 
 impl crate::Advent for Dive {
-    fn new(data: &str) -> Dive {
-        let commands: Vec<Command> = data 
+    fn new(data: &str) -> anyhow::Result<Dive> {
+        let input = Input::new(data);
+        let commands: Vec<Command> = input 
             .lines()
             .map(|d| {
                 let mut cmd_iter = d.split_whitespace();
-                let direction = Direction::from_str(cmd_iter.next().unwrap()).unwrap();
-                let count = cmd_iter.next().unwrap().parse::<usize>().unwrap();
+                let direction_token = input.expect(cmd_iter.next(), d, "a direction")?;
+                let direction: Direction = input.parse(direction_token)?;
+                let count = input.parse::<usize>(input.expect(cmd_iter.next(), direction_token, "a count")?)?;
 
-                Command { direction, count }
+                Ok(Command { direction, count })
             })
-            .collect();
-        Dive { commands }
+            .collect::<anyhow::Result<_>>()?;
+
+        // Depth in part 1 and aim in part 2 follow the same course, so checking it once covers both
+        let mut depth: usize = 0;
+        for (line, command) in input.lines().zip(&commands) {
+            depth = match command.direction {
+                Direction::Forward => depth,
+                Direction::Down => depth.checked_add(command.count)
+                    .ok_or_else(|| input.error(line, "dives too deep"))?,
+                Direction::Up => depth.checked_sub(command.count)
+                    .ok_or_else(|| input.error(line, "rises above the surface"))?,
+            };
+        }
+        Ok(Dive { commands })
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         let mut horizontal: usize = 0;
         let mut depth: usize = 0;
 
@@ -53,10 +69,10 @@ impl crate::Advent for Dive {
             Direction::Up => depth -= c.count,
         });
 
-        (horizontal * depth) as usize
+        (horizontal * depth).into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let mut horizontal = 0;
         let mut depth = 0;
         let mut aim = 0;
@@ -70,7 +86,19 @@ impl crate::Advent for Dive {
             Direction::Up => aim -= c.count,
         });
 
-        (horizontal * depth) as usize
+        (horizontal * depth).into()
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+
+    #[test]
+    fn rejects_rising_above_the_surface() {
+        let err = Dive::new("down 3\nforward 2\nup 4").err().unwrap();
+        assert_eq!(err.to_string(), "line 3, column 1: unexpected 'up 4': rises above the surface");
+    }
+}
@@ -1,4 +1,5 @@
-
+use crate::answer::Answer;
+use crate::parse::{self, Input};
 
 pub struct BinaryDiagnostic {
     data: Vec<Bin>,
@@ -12,22 +13,33 @@ fn vec_to_int(v: &Bin) -> usize {
 }
 
 impl crate::Advent for BinaryDiagnostic {
-    fn new(data: &str) -> BinaryDiagnostic {        
-        BinaryDiagnostic {        
-            data: data
+    fn new(data: &str) -> anyhow::Result<BinaryDiagnostic> {        
+        let input = Input::new(data);
+        let width = data.lines().next().unwrap_or_default().chars().count();
+        if width == 0 {
+            return Err(input.missing(data, "a binary number").into());
+        }
+        Ok(BinaryDiagnostic {        
+            data: input
                 .lines()
                 .map(|l| {
-                    l
-                        .chars()
-                        .map(|c| c.to_digit(10).unwrap().try_into().unwrap())
-                        .collect::<Bin>()                
+                    if l.chars().count() != width {
+                        return Err(input.error(l, format!("expected {} binary digits in every row", width)));
+                    }
+                    parse::chars(l)
+                        .map(|c| match c {
+                            "0" => Ok(0),
+                            "1" => Ok(1),
+                            _ => Err(input.error(c, "expected a binary digit")),
+                        })
+                        .collect::<Result<Bin, _>>()                
                 })
-                .collect(),
+                .collect::<Result<_, _>>()?,
             mid_point: data.lines().count() as f32 / 2.0
-            }
+            })
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         let columns_sum = self.get_columns_sum();
         let mut gamma_rating: Bin = Vec::new();
         for column_sum in columns_sum.iter() {
@@ -51,23 +63,23 @@ impl crate::Advent for BinaryDiagnostic {
         let gamma: usize = vec_to_int(&gamma_rating);
         let epsilon: usize = vec_to_int(&epsilon_rating);
 
-        gamma * epsilon
+        (gamma * epsilon).into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let oxygen_rating = self.get_life_support_rating_part(true);
         let co2_scrubber_rating = self.get_life_support_rating_part(false);
 
         let oxygen = vec_to_int(&oxygen_rating);
         let co2_scrubber = vec_to_int(&co2_scrubber_rating);
 
-        oxygen * co2_scrubber
+        (oxygen * co2_scrubber).into()
     }
 }
 
 impl BinaryDiagnostic {
     
-    fn get_common_bit(&self, data: &Vec<Bin>, index: usize, common: bool) -> u8 {
+    fn get_common_bit(&self, data: &[Bin], index: usize, common: bool) -> u8 {
         let bit: f32 = data
             .iter()
             .filter(|x| x[index] == 1)
@@ -111,4 +123,18 @@ impl BinaryDiagnostic {
 
         panic!("Reached the end!")
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+
+    #[test]
+    fn rejects_unsolvable_reports() {
+        let err = BinaryDiagnostic::new("").err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 1: expected a binary number");
+        let err = BinaryDiagnostic::new("00100\n1111\n10110").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 1: unexpected '1111': expected 5 binary digits in every row");
+    }
+}
@@ -1,3 +1,6 @@
+use crate::answer::Answer;
+use crate::parse::Input;
+
 pub struct GiantSquid {
     boards: Vec<Board>,
     draw_numbers: Vec<u8>
@@ -45,47 +48,56 @@ impl Board {
 }
 
 impl crate::Advent for GiantSquid {
-    fn new(data: &str) -> GiantSquid {
-        let mut iter = data.lines();
-        let draw_numbers: Vec<u8> = iter
-            .next()
-            .unwrap()
+    fn new(data: &str) -> anyhow::Result<GiantSquid> {
+        let input = Input::new(data);
+        let mut iter = input.lines();
+        let draw_numbers: Vec<u8> = input
+            .expect(iter.next(), data, "the drawn numbers")?
             .split(',')
-            .flat_map(|c| c.parse())
-            .collect();
+            .map(|c| input.parse(c))
+            .collect::<Result<_, _>>()?;
         
         let mut boards = Vec::new();
 
-        while iter.next().is_some() {
-            let data = iter
-                .by_ref()
-                .take(5)
-                .flat_map(|s| s.split_whitespace())
-                .flat_map(|c| c.parse())
-                .collect::<Vec<u8>>()
-                .try_into()
-                .unwrap();
+        while let Some(separator) = iter.next() {
+            if !separator.is_empty() {
+                return Err(input.error(separator, "expected an empty line between boards").into());
+            }
+            let mut data = [0; 25];
+            let mut previous = separator;
+            for row in 0..5 {
+                let line = input.expect(iter.next(), previous, "a board row")?;
+                let numbers = line
+                    .split_whitespace()
+                    .map(|c| input.parse(c))
+                    .collect::<Result<Vec<u8>, _>>()?;
+                if numbers.len() != 5 {
+                    return Err(input.error(line, "expected 5 numbers in a board row").into());
+                }
+                data[row * 5..row * 5 + 5].copy_from_slice(&numbers);
+                previous = line;
+            }
             boards.push(Board { data });
         }
 
-        GiantSquid { draw_numbers, boards }
+        Ok(GiantSquid { draw_numbers, boards })
     }    
 
-    fn part1(&mut self) -> usize {    
+    fn part1(&mut self) -> Answer {    
         let (board, last_draw_index) = self.boards
             .iter()
             .map(|b| (b, b.last_draw_index(&self.draw_numbers)))
             .min_by_key(|t| t.1)
             .unwrap();
-        board.winning_score(&self.draw_numbers, last_draw_index)
+        board.winning_score(&self.draw_numbers, last_draw_index).into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let (board, last_draw_index) = self.boards
             .iter()
             .map(|b| (b, b.last_draw_index(&self.draw_numbers)))
             .max_by_key(|t| t.1)
             .unwrap();
-        board.winning_score(&self.draw_numbers, last_draw_index)
+        board.winning_score(&self.draw_numbers, last_draw_index).into()
     }
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use crate::answer::Answer;
+use crate::parse::{Input, ParseError};
 
 #[derive(Debug)]
 pub struct Coordinate {
@@ -7,12 +9,10 @@ pub struct Coordinate {
 }
 
 impl Coordinate {
-    fn new(s: &str) -> Self {
-        let mut iter = s.split(',').flat_map(|c| c.parse());
-        let x = iter.next().unwrap();
-        let y = iter.next().unwrap();
+    fn parse(input: &Input, s: &str) -> Result<Self, ParseError> {
+        let (x, y) = input.split_once(s, ",")?;
 
-        Coordinate { x, y }
+        Ok(Coordinate { x: input.parse(x)?, y: input.parse(y)? })
     }
 
     fn is_straight_with(&self, other: &Self) -> bool {
@@ -36,26 +36,25 @@ pub struct HydrothermalVenture {
 }
 
 impl crate::Advent for HydrothermalVenture {
-    fn new(data: &str) -> HydrothermalVenture {
-        let lines = data
+    fn new(data: &str) -> anyhow::Result<HydrothermalVenture> {
+        let input = Input::new(data);
+        let lines = input
             .lines()
             .map(|l| {
-                let mut iter = l.split(" -> ").map(Coordinate::new);
-                let start = iter.next().unwrap();
-                let end = iter.next().unwrap();
-                Line { start, end }
+                let (start, end) = input.split_once(l, " -> ")?;
+                Ok(Line { start: Coordinate::parse(&input, start)?, end: Coordinate::parse(&input, end)? })
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
         
-        HydrothermalVenture { lines }
+        Ok(HydrothermalVenture { lines })
     }
 
-    fn part1(&mut self) -> usize {
-        count_overlap(self.lines.iter().filter(|l| !l.is_diagonal()))
+    fn part1(&mut self) -> Answer {
+        count_overlap(self.lines.iter().filter(|l| !l.is_diagonal())).into()
     }
 
-    fn part2(&mut self) -> usize {
-        count_overlap(self.lines.iter())
+    fn part2(&mut self) -> Answer {
+        count_overlap(self.lines.iter()).into()
     }
 }
 
@@ -1,39 +1,68 @@
-use std::cell::RefCell;
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::parse::Input;
+use crate::simulation::Simulation;
+
+pub const PARAMS: &[Param] = &[Param { name: "days1", default: 80, min: 0 }, Param { name: "days2", default: 256, min: 0 }];
+
+/// Number of fish of each age.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct School([usize; 9]);
+
+impl Simulation for School {
+    fn step(&mut self) {
+        let fish_by_age = &mut self.0;
+        let new_fish = fish_by_age[0];
+        (1..=8).for_each(|i| fish_by_age[i - 1] = fish_by_age[i]);
+        // Simulating many more days than the puzzle does leaves too many fish to count
+        fish_by_age[6] = fish_by_age[6].checked_add(new_fish).expect("number of fish overflows usize");
+        fish_by_age[8] = new_fish;
+    }
+}
 
 pub struct Lanternfish {
-    fish_by_age: RefCell<[usize; 9]>,
+    school: School,
+    days: [usize; 2],
 }
 
 impl Lanternfish {
     fn pass_cycles(&self, days: usize) -> usize {
-        let mut fish_by_age = self.fish_by_age.borrow_mut();
-        for _ in 0..days {
-            let new_fish = fish_by_age[0];
-            (1..=8).for_each(|i| fish_by_age[i - 1] = fish_by_age[i]);
-            fish_by_age[6] += new_fish;
-            fish_by_age[8] = new_fish;
-        }
-        
-        fish_by_age.iter().sum()
+        let mut school = self.school.clone();
+        school.run(days as u64);
+        school.0.iter().try_fold(0usize, |sum, count| sum.checked_add(*count)).expect("number of fish overflows usize")
     }
 }
 
 impl crate::Advent for Lanternfish {
-    fn new(data: &str) -> Lanternfish {
-        let fish: Vec<u8> = data.split(",").flat_map(|f| f.parse()).collect();
+    fn new(data: &str) -> anyhow::Result<Lanternfish> {
+        let input = Input::new(data);
         let mut fish_by_age = [0; 9];
-        for f in fish {
-            fish_by_age[f as usize] += 1;
+        for f in data.split(',') {
+            let age: usize = input.parse(f)?;
+            if age > 8 {
+                return Err(input.error(f, "expected an age between 0 and 8").into());
+            }
+            fish_by_age[age] += 1;
         }
-        Lanternfish { fish_by_age: RefCell::new(fish_by_age) }        
+        Ok(Lanternfish { school: School(fish_by_age), days: [PARAMS[0].default, PARAMS[1].default] })
     }
 
-    fn part1(&mut self) -> usize {
-        self.pass_cycles(80)
+    fn part1(&mut self) -> Answer {
+        self.pass_cycles(self.days[0]).into()
     }
 
-    fn part2(&mut self) -> usize {
-        // Only pass additional cycles since we already passed 80 cycles
-        self.pass_cycles(256 - 80)
+    fn part2(&mut self) -> Answer {
+        self.pass_cycles(self.days[1]).into()
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: usize) {
+        match name {
+            "days1" => self.days[0] = value,
+            _ => self.days[1] = value,
+        }
     }
 }
\ No newline at end of file
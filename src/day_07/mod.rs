@@ -1,3 +1,6 @@
+use crate::answer::Answer;
+use crate::parse::Input;
+
 trait Math {
     fn is_even(&self) -> bool;
 
@@ -18,15 +21,11 @@ trait Math {
 
 impl Math for usize {
     fn is_even(&self) -> bool {
-        self % 2 == 0
+        self.is_multiple_of(2)
     }
 
     fn abs_difference(&self, other: &usize) -> usize {
-        if self < other {
-            other - self
-        } else {
-            self - other
-        }
+        other.abs_diff(*self)
     }
 }
 
@@ -35,31 +34,33 @@ pub struct TheThreacheryOfWhales {
 }
 
 impl crate::Advent for TheThreacheryOfWhales {
-    fn new(data: &str) -> TheThreacheryOfWhales {
+    fn new(data: &str) -> anyhow::Result<TheThreacheryOfWhales> {
+        let input = Input::new(data);
         let mut crab_positions: Vec<usize> = data
             .split(',')
-            .flat_map(|c| c.parse())
-            .collect();
+            .map(|c| input.parse(c))
+            .collect::<Result<_, _>>()?;
         crab_positions.sort_unstable();
-        TheThreacheryOfWhales { crab_positions }
+        Ok(TheThreacheryOfWhales { crab_positions })
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         let median = self.median();
-        self.crab_positions.iter().map(|&p| p.abs_difference(&median)).sum()
+        self.crab_positions.iter().map(|&p| p.abs_difference(&median)).sum::<usize>().into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         (self.mean()..)
             .take(2)
             .map(|m| {
                 self.crab_positions
                     .iter()
                     .map(|&p| usize::triangle_sum(p.abs_difference(&m)))
-                    .sum()
+                    .sum::<usize>()
             })
             .min()
             .unwrap_or_default()
+            .into()
     }    
 }
 
@@ -70,7 +71,7 @@ impl TheThreacheryOfWhales {
         if len.is_even() {
             (self.crab_positions[middle - 1] + self.crab_positions[middle]) / 2
         } else {
-            self.crab_positions[middle] / 2
+            self.crab_positions[middle]
         }
     }
 
@@ -82,6 +83,29 @@ impl TheThreacheryOfWhales {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::Advent;
+    use proptest::prelude::*;
+
+    /// Fuel to align the crabs, trying every position up to the furthest crab.
+    fn cheapest(positions: &[usize], fuel: impl Fn(usize) -> usize) -> usize {
+        let furthest = positions.iter().copied().max().unwrap_or_default();
+        (0..=furthest)
+            .map(|target| positions.iter().map(|&p| fuel(p.abs_difference(&target))).sum())
+            .min()
+            .unwrap_or_default()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn matches_brute_force(positions in prop::collection::vec(0..100usize, 1..40)) {
+            let data = positions.iter().map(usize::to_string).collect::<Vec<_>>().join(",");
+            let mut crabs = TheThreacheryOfWhales::new(&data).unwrap();
+            prop_assert_eq!(crabs.part1(), cheapest(&positions, |distance| distance).into());
+            prop_assert_eq!(crabs.part2(), cheapest(&positions, usize::triangle_sum).into());
+        }
+    }
 
     #[test]
     fn triangle_sum_10() {
@@ -1,3 +1,5 @@
+use crate::answer::Answer;
+use crate::parse::{self, Input, ParseError};
 
 pub struct SevenSegmentSearch {
     sequence: Vec<Display>,
@@ -15,9 +17,9 @@ impl Display {
     fn get_unique(&self, len: usize) -> &Pattern {
         self.signal_patterns
             .iter()
-            .filter(|s| {
+            .find(|s| {
                 s.len() == len
-            }).next().unwrap()
+            }).unwrap()
     }
 
     fn get_several(&self, len: usize) -> Vec<&Pattern> {
@@ -51,9 +53,9 @@ impl Display {
         // using the 'c' and 'f' segments, one can distinguish between 2, 3 and 5
         // Find the 3 and 2, then the 'e' segment is the one that is present in 2 but not in 3
         let five_segments = self.get_several(5);
-        let three_segment = five_segments.iter().filter(|s| s.contains(&c_seg) && s.contains(&f_seg)).next().unwrap();
-        let two_segment = five_segments.iter().filter(|s| s.contains(&c_seg) && !s.contains(&f_seg)).next().unwrap();
-        let e_seg = two_segment.iter().filter(|c| !three_segment.contains(&c)).next().unwrap().clone();
+        let three_segment = five_segments.iter().find(|s| s.contains(&c_seg) && s.contains(&f_seg)).unwrap();
+        let two_segment = five_segments.iter().find(|s| s.contains(&c_seg) && !s.contains(&f_seg)).unwrap();
+        let e_seg = *two_segment.iter().find(|c| !three_segment.contains(c)).unwrap();
 
         // The three obtained segments are enough to distinguish between the non-unique-length digits
         (c_seg, f_seg, e_seg)
@@ -102,44 +104,49 @@ impl Display {
 
 
 impl crate::Advent for SevenSegmentSearch {
-    fn new(data: &str) -> SevenSegmentSearch {
-        let sequence = data
+    fn new(data: &str) -> anyhow::Result<SevenSegmentSearch> {
+        let input = Input::new(data);
+        let patterns = |s: &str, count: usize| -> Result<Vec<Pattern>, ParseError> {
+            let patterns: Vec<&str> = s.split(' ').collect();
+            if patterns.len() != count {
+                return Err(input.error(s, format!("expected {} patterns", count)));
+            }
+            patterns.iter().map(|p| {
+                match parse::chars(p).find(|c| !("a"..="g").contains(c)) {
+                    Some(segment) => Err(input.error(segment, "expected a segment between a and g")),
+                    None => Ok(p.chars().collect()),
+                }
+            }).collect()
+        };
+        let sequence = input
             .lines()
             .map(|s| {
-                let mut iter = s.split(" | ");
-                let signal_patterns: Vec<Pattern> = iter.next().unwrap().split(' ').map(|s| s.chars().collect()).collect();
-                let output_values: Vec<Pattern> = iter.next().unwrap().split(' ').map(|s| s.chars().collect()).collect();
-                let d = Display { signal_patterns, output_values };
+                let (signals, outputs) = input.split_once(s, " | ")?;
+                let d = Display { signal_patterns: patterns(signals, 10)?, output_values: patterns(outputs, 4)? };
                 //println!("Display: {:?}", d);
-                d
+                Ok(d)
 
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
 
         // let numbers_length_map = [6, 2, 5, 5, 4, 5, 6, 3, 7, 6];
-        SevenSegmentSearch { sequence, }
+        Ok(SevenSegmentSearch { sequence, })
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         self.sequence
             .iter()
             .map(|d| {
                 d.output_values
                     .iter()
-                    .filter(|v| {
-                        match v.len() {
-                            2 => true,
-                            3 => true,
-                            4 => true,
-                            7 => true,
-                            _ => false
-                        }
-                    }).count()
+                    .filter(|v| matches!(v.len(), 2 | 3 | 4 | 7))
+                    .count()
             })
-            .sum()
+            .sum::<usize>()
+            .into()
     }
 
-    fn part2(&mut self) -> usize {    
+    fn part2(&mut self) -> Answer {    
 
         //     [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 
@@ -161,6 +168,6 @@ impl crate::Advent for SevenSegmentSearch {
             })
             .sum();
         
-        result as usize
+        result.into()
     }
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
+use crate::answer::Answer;
+use crate::parse::{self, Input, ParseError};
 
 pub struct SmokeBasin {
     map: Vec<Coordinate>,
@@ -20,7 +22,7 @@ impl PartialEq for Coordinate {
 
 impl SmokeBasin {
     fn get_coordinate_ref(&self, x: usize, y: usize) -> &Coordinate {
-        return &self.map[self.width * y + x];
+        &self.map[self.width * y + x]
     }
 
     fn neighbours(&self, c: &Coordinate) -> Vec<&Coordinate> {
@@ -52,7 +54,7 @@ impl SmokeBasin {
     fn basin(&self, c: &Coordinate) -> Vec<&Coordinate> {
         let mut basin: Vec<&Coordinate> = vec![self.get_coordinate_ref(c.x, c.y)];
         let mut outer_coordinates = basin.clone();
-        while outer_coordinates.len() != 0 {
+        while !outer_coordinates.is_empty() {
             let mut new_outer_coordinates: Vec<&Coordinate> = vec![];
             outer_coordinates.iter().for_each(|outer| {
                 self.neighbours(outer).iter().filter(|n| {
@@ -71,32 +73,38 @@ impl SmokeBasin {
 }
 
 impl crate::Advent for SmokeBasin {
-    fn new(data: &str) -> SmokeBasin {      
+    fn new(data: &str) -> anyhow::Result<SmokeBasin> {      
+        let input = Input::new(data);
         let height = data.lines().count();
-        let width = data.lines().next().unwrap().chars().count();
+        let width = data.lines().next().unwrap_or_default().chars().count();
 
-        let map: Vec<Coordinate> = data
+        let map: Vec<Coordinate> = input
             .lines().enumerate()
-            .flat_map(|l| {
+            .map(|l| {
                 let (y, line) = l;
-                line
-                    .chars().enumerate()
+                if line.chars().count() != width {
+                    return Err(input.error(line, format!("expected {} heights in every row", width)));
+                }
+                parse::chars(line).enumerate()
                     .map(|c| {
                         let (x, chr) = c;                        
-                        Coordinate { x, y, val: chr.to_digit(10).unwrap() as u8 }
-                    }).collect::<Vec<Coordinate>>()                    
+                        Ok(Coordinate { x, y, val: input.parse(chr)? })
+                    }).collect::<Result<Vec<Coordinate>, ParseError>>()                    
             })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
             .collect();  
-        SmokeBasin { map, height, width }
+        Ok(SmokeBasin { map, height, width })
     }
  
-    fn part1(&mut self) -> usize {            
+    fn part1(&mut self) -> Answer {            
         let low_points = self.low_points();
         let sum: usize = low_points.iter().copied().map(|c| c.val as usize).sum();
-        sum + low_points.len()
+        (sum + low_points.len()).into()
     }
     
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let low_points = self.low_points();
         let mut basins: Vec<Vec<&Coordinate>> = low_points.iter().map(|c| self.basin(c)).collect();
         basins.sort_unstable_by_key(|b| b.len());
@@ -104,6 +112,6 @@ impl crate::Advent for SmokeBasin {
         let mut result: usize = 1;
         basins.iter().rev().take(3).for_each(|c| result *= c.len());
 
-        result
+        result.into()
     }
 }
\ No newline at end of file
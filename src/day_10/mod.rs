@@ -1,3 +1,6 @@
+use crate::answer::Answer;
+use crate::parse::{self, Input};
+
 pub struct SyntaxScoring {
     lines: Vec<Vec<char>>
 }
@@ -6,13 +9,13 @@ const OPENING_CHARS: [char; 4] = ['(', '[', '{', '<'];
 // const CLOSING_CHARS: [char; 4] = [')', ']', '{', '>'];
 
 fn match_chars(opening: &char, closing: &char) -> bool {
-    match (opening, closing) {
-        ('(', ')') | 
+    matches!(
+        (opening, closing),
+        ('(', ')') |
         ('[', ']') |
         ('{', '}') |
-        ('<', '>' ) => true,
-        _ => false
-    }
+        ('<', '>')
+    )
 }
 
 fn get_closing_char(opening: &char) -> char {
@@ -35,7 +38,7 @@ fn illegal_char_points(closing: &char) -> usize {
     }
 }
 
-fn missing_char_points(missing_chars: &Vec<char>) -> usize {    
+fn missing_char_points(missing_chars: &[char]) -> usize {    
     let mut points: usize = 0;
 
     missing_chars.iter().for_each(|c| {
@@ -54,7 +57,7 @@ fn missing_char_points(missing_chars: &Vec<char>) -> usize {
 }
 
 impl SyntaxScoring {
-    fn check_corrupt<'a> (&self, line: &'a Vec<char>) -> Result<Vec<&'a char>, &'a char> {
+    fn check_corrupt<'a> (&self, line: &'a [char]) -> Result<Vec<&'a char>, &'a char> {
         let mut currently_open: Vec<&char> = Vec::new();
         for c in line.iter() {
             if OPENING_CHARS.contains(c) {
@@ -71,40 +74,68 @@ impl SyntaxScoring {
 }
 
 impl crate::Advent for SyntaxScoring {
-    fn new(data: &str) -> SyntaxScoring {
-        let lines: Vec<Vec<char>> = data.lines().map(|line| {
-            line.chars().collect::<Vec<char>>()
-        }).collect();
-        SyntaxScoring { lines }
+    fn new(data: &str) -> anyhow::Result<SyntaxScoring> {
+        let input = Input::new(data);
+        let lines: Vec<Vec<char>> = input.lines().map(|line| {
+            let mut open = 0;
+            for c in parse::chars(line) {
+                if !"()[]{}<>".contains(c) {
+                    return Err(input.error(c, "expected a bracket"));
+                }
+                if "([{<".contains(c) {
+                    open += 1;
+                } else if open == 0 {
+                    return Err(input.error(c, "closes a chunk which was never opened"));
+                } else {
+                    open -= 1;
+                }
+            }
+            Ok(line.chars().collect::<Vec<char>>())
+        }).collect::<Result<_, _>>()?;
+        let syntax_scoring = SyntaxScoring { lines };
+        // Part 2 takes the middle score of the lines which aren't corrupted
+        if !syntax_scoring.lines.iter().any(|line| syntax_scoring.check_corrupt(line).is_ok()) {
+            return Err(input.missing(data, "a line which isn't corrupted").into());
+        }
+        Ok(syntax_scoring)
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         let mut illegal_chars: Vec<&char> = Vec::new();
         self.lines.iter().for_each(|line| {
             let res = self.check_corrupt(line);
-            match res {
-                Err(char) => illegal_chars.push(char),
-                _ => ()
-            }
+            if let Err(char) = res { illegal_chars.push(char) }
         });
         
         illegal_chars.iter().map(|c| {
             illegal_char_points(c)
-        }).sum()
+        }).sum::<usize>().into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let missing_chars: Vec<Vec<char>> = self.lines.iter().filter_map(|line| {
-            match self.check_corrupt(line) {
-                Ok(currently_open) => Some(currently_open),
-                Err(_) => None,
-            }
+            self.check_corrupt(line).ok()
         }).map(|currently_open| {
             currently_open.iter().rev().map(|c| get_closing_char(c)).collect()
         }).collect();
 
         let mut scores: Vec<usize> = missing_chars.iter().map(|mc| missing_char_points(mc)).collect();
         scores.sort();
-        scores[scores.len() / 2]
+        scores[scores.len() / 2].into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+
+    #[test]
+    fn rejects_unsolvable_lines() {
+        let err = SyntaxScoring::new("").err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 1: expected a line which isn't corrupted");
+        let err = SyntaxScoring::new("[({(<(())[]>[[{[]{<()<>>\n)(").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 1: unexpected ')': closes a chunk which was never opened");
+        assert!(SyntaxScoring::new("{([(<{}[<>[]}>{[]{[(<()>").is_err());
     }
-}
\ No newline at end of file
+}
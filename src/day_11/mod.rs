@@ -1,9 +1,17 @@
-// use owo_colors::OwoColorize;
-// use std::io::stdin; 
+use std::fmt;
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::parse::{self, Input};
+use crate::simulation::Simulation;
+
 const WIDTH: usize = 10;
 
+pub const PARAMS: &[Param] = &[Param { name: "steps", default: 100, min: 0 }];
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct DumboOctopus {
     octopuses: [[u8; WIDTH]; WIDTH],
+    steps: usize,
 }
 
 const ADJACENT_OFFSET: [(isize, isize); 8] = [
@@ -39,6 +47,11 @@ impl DumboOctopus {
         neighbours
     }
 
+    /// Octopuses which flashed during the last step, as those are the ones left at zero.
+    fn flashed(&self) -> usize {
+        self.octopuses.iter().flatten().filter(|&&energy| energy == 0).count()
+    }
+
     fn pass_cycle(&mut self) -> usize {
         self.octopuses.iter_mut().flatten().for_each(|v| *v += 1);
 
@@ -67,47 +80,89 @@ impl DumboOctopus {
         }        
         num_flashes
     }
+}
+
+impl fmt::Display for DumboOctopus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.octopuses {
+            // Octopuses which just flashed are drawn as '*'
+            let row: String = row.iter().map(|&energy| if energy == 0 { '*' } else { (b'0' + energy) as char }).collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
 
-    // fn display(&self, label: &str) -> () {
-    //     println!("{}", label);        
-    //     self.octopuses.iter().for_each(|row| {
-    //         row.iter().for_each(|val| {
-    //             if *val >= 9 {
-    //                 print!("{:<2} ", val.red());
-    //             } else if *val == 0 {
-    //                 print!("{:<2} ", val.green());
-    //             } else {
-    //                 print!("{:<2} ", val);
-    //             }               
-    //         });
-    //         println!();
-    //     });        
-    //     println!("-----------------------");
-    // }
+impl Simulation for DumboOctopus {
+    fn step(&mut self) {
+        self.pass_cycle();
+    }
+}
+
+impl crate::Visualize for DumboOctopus {
+    fn visualize(&mut self, part: u8, frame: &mut dyn FnMut(String)) {
+        let mut grid = self.clone();
+        let size = WIDTH * WIDTH;
+        frame(format!("Step 0\n{}", grid));
+        if part == 1 && self.steps == 0 {
+            return;
+        }
+        for step in 1.. {
+            let flashes = grid.pass_cycle();
+            frame(format!("Step {}, {} flashes\n{}", step, flashes, grid));
+            if (part == 1 && step == self.steps) || (part != 1 && flashes == size) {
+                break;
+            }
+        }
+    }
 }
 
 impl crate::Advent for DumboOctopus {
-    fn new(data: &str) -> DumboOctopus {
-        let octopuses: [[u8; WIDTH]; WIDTH] = data.lines().map(|l| {
-            l.chars().map(|c| c.to_digit(10).unwrap() as u8).collect::<Vec<u8>>().try_into().unwrap()
-        }).collect::<Vec<[u8; WIDTH]>>().try_into().unwrap();
-        DumboOctopus { octopuses }
+    fn new(data: &str) -> anyhow::Result<DumboOctopus> {
+        let input = Input::new(data);
+        let mut octopuses = [[0; WIDTH]; WIDTH];
+        let mut lines = input.lines();
+        let mut previous = data;
+        for row in octopuses.iter_mut() {
+            let line = input.expect(lines.next(), previous, &format!("{} rows", WIDTH))?;
+            if line.chars().count() != WIDTH {
+                return Err(input.error(line, format!("expected {} energy levels in a row", WIDTH)).into());
+            }
+            for (energy, c) in row.iter_mut().zip(parse::chars(line)) {
+                *energy = input.parse(c)?;
+            }
+            previous = line;
+        }
+        if let Some(line) = lines.next() {
+            return Err(input.error(line, format!("expected only {} rows", WIDTH)).into());
+        }
+        Ok(DumboOctopus { octopuses, steps: PARAMS[0].default })
     }
 
-    fn part1(&mut self) -> usize {
-        let mut num_flashes: usize = 0;
-        for _ in 0..100 {
-            num_flashes += self.pass_cycle();
-        }        
-        num_flashes
+    fn part1(&mut self) -> Answer {
+        let mut grid = self.clone();
+        grid.run_summing(self.steps as u64, |grid| grid.flashed() as u64).into()
     }
 
-    fn part2(&mut self) -> usize {
-        let mut passed_cycles: usize = 100;
-        let size = WIDTH * WIDTH;
-        while self.pass_cycle() != size {
-            passed_cycles += 1;
+    fn part2(&mut self) -> Answer {
+        let mut grid = self.clone();
+        // A grid at zero before the first step hasn't flashed yet
+        grid.step();
+        match grid.run_until(|grid| grid.flashed() == WIDTH * WIDTH) {
+            Ok(steps) => (1 + steps).into(),
+            Err(cycle) => panic!("The octopuses never all flash at once, {}", cycle),
         }
-        passed_cycles + 1
+    }
+
+    fn visualization(&mut self) -> Option<&mut dyn crate::Visualize> {
+        Some(self)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, _name: &str, value: usize) {
+        self.steps = value;
     }
 }
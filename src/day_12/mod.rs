@@ -1,24 +1,30 @@
-use std::rc::{Rc, Weak};
-use std::cell::RefCell;
+use std::sync::{Arc, OnceLock, Weak};
 use std::cmp::Ordering;
 use std::fmt;
 use itertools::Itertools;
+use crate::answer::Answer;
+use crate::parse::Input;
 
 pub struct PassagePassing{
-    caves: Vec<Rc<Cave>>
+    caves: Vec<Arc<Cave>>
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 struct Cave {
     value: String,
-    connected: RefCell<Vec<Weak<Cave>>>,
+    // Set once all caves exist, so they can point at each other
+    connected: OnceLock<Vec<Weak<Cave>>>,
     small: bool,
 }
 
 impl Cave {
     fn new(value: String) -> Cave {
         let small: bool = value.chars().any(|c| c.is_ascii_lowercase());
-        Cave { value, connected: RefCell::new(Vec::new()), small }
+        Cave { value, connected: OnceLock::new(), small }
+    }
+
+    fn connected(&self) -> &[Weak<Cave>] {
+        self.connected.get().map_or(&[], |connected| connected)
     }
 }
 
@@ -52,25 +58,25 @@ enum Part {
 }
 
 struct CaveTraversal {
-    stack: Vec<Rc<Cave>>,
+    stack: Vec<Arc<Cave>>,
     checked: Vec<usize>, // Number of checked per node    
     part: Part,
     checked_twice_index: Option<usize>,
 }
 
 impl CaveTraversal {
-    pub fn new(root:Rc<Cave>, part: Part) -> Self {
+    pub fn new(root:Arc<Cave>, part: Part) -> Self {
         CaveTraversal { stack: vec![root], checked: vec![0], part, checked_twice_index: None }
     }
 
     // Returns the next unchecked neighbour from the last cave in the stack
-    fn inner_next(&mut self) -> (Option<Rc<Cave>>, usize) {      
+    fn inner_next(&mut self) -> (Option<Arc<Cave>>, usize) {      
         let index = self.stack.len() - 1;
         
         let current_cave = &self.stack[index];
         let current_checked: usize = self.checked[index];
         let mut skipped: usize = 0;
-        let connected = current_cave.connected.borrow();
+        let connected = current_cave.connected();
         
         for cave in connected.iter().skip(current_checked) {
             let neighbour = cave.upgrade().unwrap();
@@ -82,22 +88,22 @@ impl CaveTraversal {
                     Part::Two => {
                         if neighbour.value != "start" && self.checked_twice_index.is_none() {
                             self.checked_twice_index = Some(index + 1);
-                        return (Some(Rc::clone(&neighbour)), skipped);
+                        return (Some(Arc::clone(&neighbour)), skipped);
                         }
                         skipped += 1;
                     }
                 }
             } else {
-                return (Some(Rc::clone(&neighbour)), skipped);
+                return (Some(Arc::clone(&neighbour)), skipped);
             }
         }
         (None, skipped)
     }
 
     // Walks backward till it finds a cave that not all neighbours were checked
-    fn move_to_unchecked(&mut self) -> () {
+    fn move_to_unchecked(&mut self) {
         let mut current_index = self.stack.len() - 1;
-        while self.checked[current_index] >= self.stack[current_index].connected.borrow().len() {
+        while self.checked[current_index] >= self.stack[current_index].connected().len() {
             if let Some(chi) = self.checked_twice_index {
                 if current_index == chi {
                     self.checked_twice_index = None;
@@ -114,20 +120,20 @@ impl CaveTraversal {
 }
 
 impl Iterator for CaveTraversal {
-    type Item = Vec<Rc<Cave>>;
+    type Item = Vec<Arc<Cave>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.stack.len() != 0 {
+        while !self.stack.is_empty() {
             let (next, skipped) = self.inner_next();             
             *self.checked.last_mut().unwrap() += skipped + 1;
             if let Some(next) = next {
                 if next.value == "end" {
                     let mut res = self.stack.clone();
-                    res.push(Rc::clone(&next));
+                    res.push(Arc::clone(&next));
                     self.move_to_unchecked();
                     return Some(res);                    
                 } else {
-                    self.stack.push(Rc::clone(&next));
+                    self.stack.push(Arc::clone(&next));
                     self.checked.push(0);                    
                 }
             } else {
@@ -140,76 +146,86 @@ impl Iterator for CaveTraversal {
 }
 
 impl crate::Advent for PassagePassing {
-    fn new(data: &str) -> PassagePassing {
+    fn new(data: &str) -> anyhow::Result<PassagePassing> {
+        let input = Input::new(data);
+        let connections = input.lines().map(|line| {
+            let (lhs, rhs) = input.split_once(line, "-")?;
+            for name in [lhs, rhs] {
+                if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+                    return Err(input.error(name, "expected a cave name made of letters"));
+                }
+            }
+            if lhs == rhs {
+                return Err(input.error(rhs, "a cave can't connect to itself"));
+            }
+            Ok((lhs, rhs))
+        }).collect::<Result<Vec<_>, _>>()?;
+
         // Construct unique caves
-        let mut caves: Vec<Rc<Cave>> = Vec::new();
-        data.lines().flat_map(|line| {
-            line.split("-").map(|c| {
-                Rc::new(Cave::new(c.to_string()))
-            }).collect::<Vec<Rc<Cave>>>()
-        }).for_each(|cave| {
-            if let None = caves.iter().find(|c| c.value == cave.value) {
-                caves.push(cave);
+        let mut caves: Vec<Arc<Cave>> = Vec::new();
+        connections.iter().flat_map(|(lhs, rhs)| [lhs, rhs]).for_each(|name| {
+            if !caves.iter().any(|c| c.value == *name) {
+                caves.push(Arc::new(Cave::new(name.to_string())));
             }
         });
+        for name in ["start", "end"] {
+            if !caves.iter().any(|c| c.value == name) {
+                return Err(input.missing(data, &format!("a connection to the '{}' cave", name)).into());
+            }
+        }
 
         // Connect caves
-        data.lines().for_each(|line| {
-            if let Some((lhs_cave_str, rhs_cave_str)) = line.split_once("-") {                
-                let lhs_cave = caves.iter().find(|c| c.value == lhs_cave_str.to_string()).unwrap();
-                let rhs_cave = caves.iter().find(|c| c.value == rhs_cave_str.to_string()).unwrap();
-
-                let lhs_connection = Rc::downgrade(lhs_cave);
-                let rhs_connection = Rc::downgrade(rhs_cave);
-
-                let mut lhs_connected = lhs_cave.connected.borrow_mut();
-                let mut rhs_connected = rhs_cave.connected.borrow_mut();
-
-                lhs_connected.push(rhs_connection);
-                rhs_connected.push(lhs_connection);
-            }
+        let index = |name: &str| caves.iter().position(|c| c.value == name).unwrap();
+        let mut connected: Vec<Vec<Weak<Cave>>> = vec![vec![]; caves.len()];
+        connections.iter().for_each(|(lhs_cave_str, rhs_cave_str)| {
+            let lhs = index(lhs_cave_str);
+            let rhs = index(rhs_cave_str);
+
+            connected[lhs].push(Arc::downgrade(&caves[rhs]));
+            connected[rhs].push(Arc::downgrade(&caves[lhs]));
         }); 
-        PassagePassing { caves }
+        caves.iter().zip(connected).for_each(|(cave, connected)| cave.connected.set(connected).unwrap());
+        Ok(PassagePassing { caves })
     }
 
-    fn part1(&mut self) -> usize {  
-        let start = Rc::clone(self.caves.iter().find(|cave| cave.value == "start").unwrap());
+    fn part1(&mut self) -> Answer {  
+        let start = Arc::clone(self.caves.iter().find(|cave| cave.value == "start").unwrap());
         let cave_traversal = CaveTraversal::new(start, Part::One);
         let mut num_paths: usize = 0;
 
         for _path in cave_traversal {
             num_paths += 1;
         }        
-        num_paths
+        num_paths.into()
     }
 
-    fn part2(&mut self) -> usize {
-        let start = Rc::clone(self.caves.iter().find(|cave| cave.value == "start").unwrap());        
-        let cave_traversal = CaveTraversal::new(Rc::clone(&start), Part::Two);
+    fn part2(&mut self) -> Answer {
+        let start = Arc::clone(self.caves.iter().find(|cave| cave.value == "start").unwrap());        
+        let cave_traversal = CaveTraversal::new(Arc::clone(&start), Part::Two);
         let mut num_paths: usize = 0;
         for _path in cave_traversal {
             num_paths += 1;
         }
-        num_paths
+        num_paths.into()
     }
 }
 
 impl fmt::Display for PassagePassing {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "\n")?;
+        writeln!(f)?;
         for cave in &self.caves {
-            let connected: Vec<String> = cave.connected.borrow().iter().map(|c| c.upgrade().unwrap().value.clone()).collect();
+            let connected: Vec<String> = cave.connected().iter().map(|c| c.upgrade().unwrap().value.clone()).collect();
             let connected_str = format!("{:?}", connected);
-            write!(f, "\t{} -> {}\n", cave.value, connected_str)?;
+            writeln!(f, "\t{} -> {}", cave.value, connected_str)?;
         }
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
 impl PassagePassing {
     #[allow(dead_code)]
-    fn order_paths(&self, paths: Vec<Vec<Rc<Cave>>>) -> Vec<Vec<Rc<Cave>>> {
-        let paths: Vec<Vec<Rc<Cave>>> = paths.into_iter().sorted_by(|a, b| {
+    fn order_paths(&self, paths: Vec<Vec<Arc<Cave>>>) -> Vec<Vec<Arc<Cave>>> {
+        let paths: Vec<Vec<Arc<Cave>>> = paths.into_iter().sorted_by(|a, b| {
             let mut x = 0;
             while x < a.len() && x < b.len() {
                 let ord = a[x].value.cmp(&b[x].value);
@@ -218,7 +234,7 @@ impl PassagePassing {
                 }
                 x += 1;
             }
-            return Ordering::Equal;
+            Ordering::Equal
         }).collect();
         paths
     }
@@ -1,17 +1,24 @@
 use std::fmt;
 use super::euclidean::{Coordinate, Axis};
+use crate::answer::Answer;
+use crate::ocr;
+use crate::parse::{Input, ParseError};
 
 pub struct TransparentOrigami {
-    coordinates: Vec<Coordinate<u32>>,
+    paper: Paper,
     folds: Vec<Fold>,
-    num_folded: usize,
+}
+
+/// The dots on the sheet, folded or not.
+#[derive(Clone)]
+struct Paper {
+    coordinates: Vec<Coordinate<u32>>,
     width: u32,
     height: u32
 }
 
-impl TransparentOrigami {    
-    fn fold(&mut self) -> () {
-        let fold = &self.folds[self.num_folded];
+impl Paper {    
+    fn fold(&mut self, fold: &Fold) {
         self.coordinates.iter_mut().filter(|c| {
             match fold.axis {
                 Axis::X => {c.x > fold.value},
@@ -26,35 +33,52 @@ impl TransparentOrigami {
 
         self.coordinates.sort();
         self.coordinates.dedup();
-        self.num_folded += 1;
         match fold.axis {
             Axis::X => self.width = fold.value,
             Axis::Y => self.height = fold.value
         }
     }
+
+    /// Renders the paper row by row, marking dots with '#'.
+    fn sheet(&self) -> Vec<String> {
+        let mut rows = vec![vec!['.'; self.width as usize]; self.height as usize];
+        for c in &self.coordinates {
+            rows[c.y as usize][c.x as usize] = '#';
+        }
+        rows.into_iter().map(|row| row.into_iter().collect()).collect()
+    }
+}
+
+impl TransparentOrigami {
+    /// A copy of the paper after the first `count` folds.
+    fn folded(&self, count: usize) -> Paper {
+        let mut paper = self.paper.clone();
+        self.folds.iter().take(count).for_each(|fold| paper.fold(fold));
+        paper
+    }
 }
 
-impl fmt::Display for TransparentOrigami {
+impl fmt::Display for Paper {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {        
-        write!(f, "TransparentOrigami ({} x {})\n", self.width, self.height)?;
+        writeln!(f, "Paper ({} x {})", self.width, self.height)?;
         let line_numbers: String = (0..self.width).fold(
             String::new(), 
             |acc, num: u32| {acc + format!("{: >2}", &num).as_str()}
         );
-        write!(f, "\t X {}\n", line_numbers)?;
+        writeln!(f, "\t X {}", line_numbers)?;
         for y in 0..self.height {
             let mut line: String = String::new();
             for x in 0..self.width {
                 let coordinate = self.coordinates.iter().find(|c| c.x == x && c.y == y);
-                if let Some(_) = coordinate {
+                if coordinate.is_some() {
                     line.push('⬛');
                 } else {
                     line.push('⬜');
                 }
             }
-            write!(f, "\t{: >2} {}\n", y, line)?;
+            writeln!(f, "\t{: >2} {}", y, line)?;
         }
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
@@ -64,47 +88,63 @@ struct Fold {
 }
 
 impl crate::Advent for TransparentOrigami {
-    fn new(data: &str) -> TransparentOrigami {
-        let (coordinates_data, folds_data) = data.split_once("\r\n\r\n").unwrap_or_else(
-            || {data.split_once("\n\n").unwrap()}
-        );
-        let coordinates: Vec<Coordinate<u32>> = coordinates_data
-            .lines()
+    fn new(data: &str) -> anyhow::Result<TransparentOrigami> {
+        let input = Input::new(data);
+        let mut lines = input.lines();
+        let coordinates: Vec<Coordinate<u32>> = lines
+            .by_ref()
+            .take_while(|line| !line.is_empty())
             .map(|c| {
-                let (x, y) = c.split_once(",").unwrap();
-                Coordinate { x: x.parse().unwrap(), y: y.parse().unwrap() }
-            }).collect();
+                let (x, y) = input.split_once(c, ",")?;
+                Ok(Coordinate { x: input.parse(x)?, y: input.parse(y)? })
+            }).collect::<Result<_, ParseError>>()?;
 
-        let folds: Vec<Fold> = folds_data
-            .lines()
+        let folds: Vec<Fold> = lines
             .map(|line| {
-                let (text, value) = line.split_once("=").unwrap();
-                let axis = match text.chars().last().unwrap() {
-                    'x' => Axis::X,
-                    'y' => Axis::Y,
-                    axis_label => panic!("Invalid axis label: {}", axis_label)
+                let instruction = input.strip_prefix(line, "fold along ")?;
+                let (text, value) = input.split_once(instruction, "=")?;
+                let axis = match text {
+                    "x" => Axis::X,
+                    "y" => Axis::Y,
+                    axis_label => return Err(input.error(axis_label, "expected axis x or y"))
                 };
-                Fold { axis, value: value.parse().unwrap() }
-            }).collect();
-        let max_x = coordinates.iter().map(|c| c.x).max().unwrap();
-        let max_y = coordinates.iter().map(|c| c.y).max().unwrap();
-        TransparentOrigami { coordinates, folds, num_folded: 0, width: max_x + 1, height: max_y + 1 }
+                Ok(Fold { axis, value: input.parse(value)? })
+            }).collect::<Result<_, ParseError>>()?;
+        if coordinates.is_empty() || folds.is_empty() {
+            return Err(input.missing(data, "dots followed by an empty line and fold instructions").into());
+        }
+        let max_x = coordinates.iter().map(|c| c.x).max().unwrap_or_default();
+        let max_y = coordinates.iter().map(|c| c.y).max().unwrap_or_default();
+        Ok(TransparentOrigami { paper: Paper { coordinates, width: max_x + 1, height: max_y + 1 }, folds })
     }
 
-    fn part1(&mut self) -> usize {
-        self.fold();
-        self.coordinates.len()
+    fn part1(&mut self) -> Answer {
+        self.folded(1).coordinates.len().into()
     }
     
-    fn part2(&mut self) -> usize {
-        let remaining_folds = self.folds.len() - 2;
-        for _ in 0..=remaining_folds {
-            self.fold();
+    fn part2(&mut self) -> Answer {
+        let paper = self.folded(self.folds.len());
+        // Not every sheet folds into letters (e.g. the example), the runner reports the
+        // unrecognised glyphs of the paper answered instead
+        match ocr::recognize(&paper.coordinates) {
+            Ok(code) => code.into(),
+            Err(_) => paper.sheet().into(),
         }
+    }
 
-        // This is the actual solution -- displayed as colored squares
-        // println!("Result: {}", self);
+    fn visualization(&mut self) -> Option<&mut dyn crate::Visualize> {
+        Some(self)
+    }
+}
 
-        self.coordinates.len()
+impl crate::Visualize for TransparentOrigami {
+    fn visualize(&mut self, part: u8, frame: &mut dyn FnMut(String)) {
+        let folds = if part == 1 { 1 } else { self.folds.len() };
+        let mut paper = self.paper.clone();
+        frame(paper.sheet().join("\n"));
+        for fold in self.folds.iter().take(folds) {
+            paper.fold(fold);
+            frame(paper.sheet().join("\n"));
+        }
     }
 }
\ No newline at end of file
@@ -1,9 +1,17 @@
 use std::collections::HashMap;
 use std::fmt;
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::parse::Input;
 
+pub const PARAMS: &[Param] = &[Param { name: "steps1", default: 10, min: 0 }, Param { name: "steps2", default: 40, min: 0 }];
+
+#[derive(Clone)]
 pub struct ExtendedPolymerization {
     insertion_rules: Vec<InsertionRule>,
-    pair_count: HashMap<[char; 2], usize>
+    pair_count: HashMap<[char; 2], usize>,
+    first: char,
+    steps: [usize; 2],
 }
 
 impl ExtendedPolymerization {
@@ -14,46 +22,45 @@ impl ExtendedPolymerization {
                 let lhs_key = [*lhs, insertion_rule.output];
                 let rhs_key = [insertion_rule.output, *rhs];
                 
-                new_pair_count.entry(lhs_key).and_modify(|v| *v += count).or_insert(*count);
-                new_pair_count.entry(rhs_key).and_modify(|v| *v += count).or_insert(*count);
+                // Running many more steps than the puzzle does grows the counts past a usize
+                for key in [lhs_key, rhs_key] {
+                    let pairs = new_pair_count.entry(key).or_default();
+                    *pairs = pairs.checked_add(*count).expect("number of pairs overflows usize");
+                }
                 new_pair_count.entry(insertion_rule.input).and_modify(|val| *val -= count);
             }
         }
         self.pair_count = new_pair_count;
     }
 
-    fn occurances(&self) -> HashMap<char, f64> {
-        let mut occurances: HashMap<char, f64> = HashMap::new();
-        for (key, val) in &self.pair_count {            
-            for side in key {
-                let real_val =  *val as f64 / 2_f64;
-                occurances.entry(*side).and_modify(|v| *v += real_val).or_insert(real_val);
-            }
+    fn occurances(&self) -> HashMap<char, usize> {
+        // Every element but the first one of the polymer ends exactly one pair
+        let mut occurances: HashMap<char, usize> = HashMap::from([(self.first, 1)]);
+        for ([_, rhs], count) in self.pair_count.iter().filter(|(_, count)| **count > 0) {
+            let elements = occurances.entry(*rhs).or_default();
+            *elements = elements.checked_add(*count).expect("number of elements overflows usize");
         }
-        occurances.iter_mut().for_each(|(_, v)| *v = v.ceil());
-
         occurances
     }
 
-    fn min_max(&self, occurances: HashMap<char, f64>) -> usize {        
-        let values: Vec<usize> = occurances.iter().map(|(_, v)| *v as usize).collect();
-        let min_val = values.iter().min().unwrap().clone();
-        let max_val = values.iter().max().unwrap().clone();
+    fn min_max(&self, occurances: HashMap<char, usize>) -> usize {        
+        let min_val = *occurances.values().min().unwrap();
+        let max_val = *occurances.values().max().unwrap();
         max_val - min_val
     }
 }
 
 impl fmt::Display for ExtendedPolymerization {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Current count:\n")?;
+        writeln!(f, "Current count:")?;
         for (k, v) in self.pair_count.iter().filter(|(_, v)| **v > 0) {
-            write!(f, "{:?} -> {}\n", k, v)?;
+            writeln!(f, "{:?} -> {}", k, v)?;
         }
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct InsertionRule {
     input: [char; 2],
     output: char
@@ -71,35 +78,104 @@ fn construct_pairs_count(template: Vec<char>) -> HashMap<[char; 2], usize> {
 }
 
 impl crate::Advent for ExtendedPolymerization {
-    fn new(data: &str) -> ExtendedPolymerization {
-        let polymer_template: Vec<char> =  data.lines().next().unwrap().chars().collect();
-        let insertion_rules =  data.lines().skip(2).map(|line| {
-            let (input, output) = line.split_once(" -> ").unwrap();
-            InsertionRule {
-                input: input.chars().collect::<Vec<char>>().try_into().unwrap(),
-                output: output.chars().next().unwrap()
+    fn new(data: &str) -> anyhow::Result<ExtendedPolymerization> {
+        let parser = Input::new(data);
+        let mut lines = parser.lines();
+        let template = parser.expect(lines.next(), data, "a polymer template")?;
+        if template.is_empty() {
+            return Err(parser.missing(template, "a polymer template").into());
+        }
+        let polymer_template: Vec<char> = template.chars().collect();
+        match lines.next() {
+            Some("") => (),
+            Some(line) => return Err(parser.error(line, "expected an empty line after the template").into()),
+            None => return Err(parser.missing(template, "insertion rules").into()),
+        }
+        let insertion_rules = lines.map(|line| {
+            let (input, output) = parser.split_once(line, " -> ")?;
+            let input: [char; 2] = input.chars().collect::<Vec<char>>().try_into()
+                .map_err(|_| parser.error(input, "expected a pair of elements"))?;
+            let mut output_chars = output.chars();
+            match (output_chars.next(), output_chars.next()) {
+                (Some(output), None) => Ok(InsertionRule { input, output }),
+                _ => Err(parser.error(output, "expected a single element")),
             }
-        }).collect();             
+        }).collect::<Result<_, _>>()?;             
+        let first = polymer_template[0];
         let pair_count = construct_pairs_count(polymer_template);
 
-        ExtendedPolymerization { insertion_rules, pair_count }
+        Ok(ExtendedPolymerization { insertion_rules, pair_count, first, steps: [PARAMS[0].default, PARAMS[1].default] })
     }
 
-    fn part1(&mut self) -> usize {
-        for _ in 0..10  {
-            self.run_rules();
+    fn part1(&mut self) -> Answer {
+        let mut polymer = self.clone();
+        for _ in 0..self.steps[0] {
+            polymer.run_rules();
         }            
-        let occurances = self.occurances();
-        self.min_max(occurances)
+        let occurances = polymer.occurances();
+        polymer.min_max(occurances).into()
         
     }
     
-    fn part2(&mut self) -> usize {
-        for _ in 0..30 {
-            self.run_rules();
+    fn part2(&mut self) -> Answer {
+        let mut polymer = self.clone();
+        for _ in 0..self.steps[1] {
+            polymer.run_rules();
+        }
+
+        let occurances = polymer.occurances();
+        polymer.min_max(occurances).into()
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: usize) {
+        match name {
+            "steps1" => self.steps[0] = value,
+            _ => self.steps[1] = value,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    /// Difference between the most and least common element, writing out the whole polymer.
+    fn written_out(template: &str, rules: &HashMap<String, String>, steps: usize) -> usize {
+        let mut polymer: Vec<char> = template.chars().collect();
+        for _ in 0..steps {
+            let mut next = vec![polymer[0]];
+            for pair in polymer.windows(2) {
+                if let Some(inserted) = rules.get(&pair.iter().collect::<String>()) {
+                    next.extend(inserted.chars());
+                }
+                next.push(pair[1]);
+            }
+            polymer = next;
         }
+        let counts = polymer.into_iter().counts();
+        counts.values().max().unwrap() - counts.values().min().unwrap()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1000))]
 
-        let occurances = self.occurances();
-        self.min_max(occurances)
+        #[test]
+        fn matches_written_out_polymer(
+            template in "[BCHN]{2,8}",
+            rules in prop::collection::hash_map("[BCHN]{2}", "[BCHN]", 1..16),
+            steps in (0..8usize, 0..8usize),
+        ) {
+            let rule_lines = rules.iter().map(|(pair, inserted)| format!("{} -> {}", pair, inserted)).join("\n");
+            let mut polymer = ExtendedPolymerization::new(&format!("{}\n\n{}", template, rule_lines)).unwrap();
+            polymer.steps = [steps.0, steps.1];
+            prop_assert_eq!(polymer.part1(), written_out(&template, &rules, steps.0).into());
+            prop_assert_eq!(polymer.part2(), written_out(&template, &rules, steps.1).into());
+        }
     }
-}
\ No newline at end of file
+}
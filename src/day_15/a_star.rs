@@ -1,4 +1,9 @@
-use super::euclidean::{Point, Coordinate};
+// Alternative to the Dijkstra in `mod.rs`, using A* with the manhattan distance to the goal.
+
+use std::collections::HashMap;
+use crate::answer::Answer;
+use crate::euclidean::{Point, Coordinate};
+use crate::parse::{self, Input};
 
 // Aliases
 type P = Point<u8, usize>;
@@ -56,13 +61,13 @@ fn copy_grid(grid: &Grid, n: usize) -> Grid{
     let mut new_map : Map = vec![];
 
     for y in 0..n {        
-        for (gy, line) in grid.map.iter().enumerate() {
+        for line in grid.map.iter() {
             let mut new_line: Vec<P> = vec![];
             for x in 0..n {
-                for (gx, p) in line.iter().enumerate() {
+                for p in line.iter() {
                     let mut new_val = p.val + x as u8 + y as u8;
                     if new_val > 9 {
-                        new_val = new_val % 9;
+                        new_val %= 9;
                     }
                     let new_point = Point {
                         val: new_val,
@@ -81,28 +86,34 @@ fn copy_grid(grid: &Grid, n: usize) -> Grid{
 }
 
 impl crate::Advent for Chiton {
-    fn new(data: &str) -> Chiton {
-        let data: Map = data.lines().enumerate().map(|(y, line)| {
-            let points: Line = line.chars().enumerate().map(|(x, c)| {
-                let val: u8 = c.to_digit(10).unwrap() as u8;
+    fn new(data: &str) -> anyhow::Result<Chiton> {
+        let input = Input::new(data);
+        let width = data.lines().next().unwrap_or_default().chars().count();
+        if width == 0 {
+            return Err(input.missing(data, "a risk level map").into());
+        }
+        let data: Map = input.lines().enumerate().map(|(y, line)| {
+            if line.chars().count() != width {
+                return Err(input.error(line, format!("expected {} risk levels in every row", width)));
+            }
+            parse::chars(line).enumerate().map(|(x, c)| {
+                let val: u8 = input.parse(c)?;
                 let coordinate: Coordinate<usize> = Coordinate { x, y };
-                Point { val, coordinate }
-            }).collect();
-            points
-        }).collect();
+                Ok(Point { val, coordinate })
+            }).collect()
+        }).collect::<Result<_, _>>()?;
         let part1 = Grid::new(data);
         let part2 = copy_grid(&part1, 5);
-        Chiton {map_p1: part1, map_p2: part2}
+        Ok(Chiton {map_p1: part1, map_p2: part2})
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         let start = self.map_p1.get_point(0, 0);
         let goal = self.map_p1.get_point(self.map_p1.width - 1, self.map_p1.height - 1);
         let heuristic = |current: &P, goal: &P| {
             let x = goal.coordinate.x - current.coordinate.x;
             let y = goal.coordinate.y - current.coordinate.y;
-            let future_path = x + y;
-            future_path
+            x + y
         };
         let path = a_star(&self.map_p1, start, goal, heuristic);
 
@@ -115,17 +126,16 @@ impl crate::Advent for Chiton {
             None => 0
         };
 
-        cost - start.val as usize
+        (cost - start.val as usize).into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let start = self.map_p2.get_point(0, 0);
         let goal = self.map_p2.get_point(self.map_p2.width - 1, self.map_p2.height - 1);
         let heuristic = |current: &P, goal: &P| {
             let x = goal.coordinate.x - current.coordinate.x;
             let y = goal.coordinate.y - current.coordinate.y;
-            let future_path = x + y;
-            future_path
+            x + y
         };
         let path = a_star(&self.map_p2, start, goal, heuristic);
 
@@ -138,18 +148,16 @@ impl crate::Advent for Chiton {
             None => 0
         };
 
-        cost - start.val as usize
+        (cost - start.val as usize).into()
     }
 }
 
-use std::collections::HashMap;
-
 fn reconstuct_path<'a>(came_from:  &HashMap<&'a P, &'a P>, current: &'a P) -> Vec<&'a P> {    
     let mut total_path: Vec<&P> = vec![current];        
     let mut previous_point = came_from.get(current);    
     while previous_point.is_some() {
         if let Some(p) = previous_point {
-            total_path.insert(0, p.clone());
+            total_path.insert(0, *p);
             previous_point = came_from.get(p);
         }
     }
@@ -169,9 +177,9 @@ fn a_star<'a>(grid: &'a Grid, start: &'a P, goal: &'a P, heuristic: fn(&P, &P) -
     g_score.insert(start, 0);
     
     let mut f_score: HashMap<&P, usize> = grid.map.iter().flatten().map(|p| (p, usize::MAX)).collect();
-    f_score.insert(start, heuristic(&start, goal));
+    f_score.insert(start, heuristic(start, goal));
 
-    while open_set.len() != 0 {
+    while !open_set.is_empty() {
         let min_f = open_set.iter().map(|p| {
             (p, f_score.get(p).unwrap())
         }).min_by(|a, b| {
@@ -183,8 +191,6 @@ fn a_star<'a>(grid: &'a Grid, start: &'a P, goal: &'a P, heuristic: fn(&P, &P) -
             return Some(reconstuct_path(&came_from, current))
         }
 
-        // display(grid, &open_set, current, &reconstuct_path(&came_from, current));
-
         let pos = open_set.iter().position(|p| *p == current).unwrap();
         open_set.remove(pos);
 
@@ -204,32 +210,3 @@ fn a_star<'a>(grid: &'a Grid, start: &'a P, goal: &'a P, heuristic: fn(&P, &P) -
     }
     None
 }
-
-use owo_colors::OwoColorize;
-use std::io;
-
-fn input() -> () {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();    
-}
-
-fn display(grid: &Grid, open_set: &Vec<&P>, current: &P, path: &Vec<&P>) -> () {
-    for line in &grid.map {        
-        for p in line {
-            if p == current {
-                print!("{:<2}", p.val.red());
-            }
-            else if path.contains(&p) {
-                print!("{:<2}", p.val.green());
-            }
-            else if open_set.contains(&p) {
-                print!("{:<2}", p.val.bright_white());
-            } else {
-                print!("{:<2}", p.val.fg_rgb::<100, 252,218>())
-            }            
-        }
-        print!("\n");
-    }
-    println!("---------------------");
-    input();
-}
\ No newline at end of file
@@ -1,37 +1,62 @@
 use std::collections::BinaryHeap;
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::parse::{self, Input};
+
+pub mod a_star;
+
+pub const PARAMS: &[Param] = &[Param { name: "tiles", default: 5, min: 1 }];
 
 pub struct Chiton {
     maze: Vec<Vec<i32>>,
-    expanded: Vec<Vec<i32>>
+    tiles: usize,
 }
 
 impl crate::Advent for Chiton {
-    fn new(data: &str) -> Chiton {
-        let maze = data.lines()
-            .map(|l| l.bytes().map(|c| (c - b'0') as i32).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let expanded = (0..(5*maze.len()))
-            .map(|x| (0..(5*maze[0].len()))
+    fn new(data: &str) -> anyhow::Result<Chiton> {
+        let input = Input::new(data);
+        let width = data.lines().next().unwrap_or_default().chars().count();
+        if width == 0 {
+            return Err(input.missing(data, "a risk level map").into());
+        }
+        let maze = input.lines()
+            .map(|l| {
+                if l.chars().count() != width {
+                    return Err(input.error(l, format!("expected {} risk levels in every row", width)));
+                }
+                parse::chars(l).map(|c| input.parse(c)).collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Chiton { maze, tiles: PARAMS[0].default })
+    }
+
+    fn part1(&mut self) -> Answer {
+        shortest_path(&self.maze).into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        let maze = &self.maze;
+        let expanded = (0..(self.tiles*maze.len()))
+            .map(|x| (0..(self.tiles*maze[0].len()))
             .map(|y| {
                 let cost = maze[x % maze.len()][y % maze[0].len()]
                 + (x / maze.len()) as i32
                 + (y / maze[0].len()) as i32;
-                if cost < 10 {cost} else {cost - 9}
+                // Risk levels above 9 wrap back around to 1, more than once for larger tilings
+                if cost < 10 {cost} else {(cost - 1) % 9 + 1}
             })
             .collect::<Vec<_>>()
-
-            
             )
             .collect::<Vec<_>>();
-        Chiton { maze, expanded }
+        shortest_path(&expanded).into()
     }
 
-    fn part1(&mut self) -> usize {
-        shortest_path(&self.maze) as usize
+    fn params(&self) -> &'static [Param] {
+        PARAMS
     }
 
-    fn part2(&mut self) -> usize {
-        shortest_path(&self.expanded) as usize
+    fn set_param(&mut self, _name: &str, value: usize) {
+        self.tiles = value;
     }
 }
 
@@ -44,7 +69,9 @@ fn shortest_path(maze: &[Vec<i32>]) -> i32 {
     while let Some((cost, x, y)) = q.pop() {
         if (x, y) == goal { return -cost; }
         if -cost > dist[x][y] { continue; }
-        for (x1,y1) in [(x-1,y), (x+1,y), (x,y-1), (x,y+1)] {
+        let up = x.checked_sub(1).map(|x1| (x1, y));
+        let left = y.checked_sub(1).map(|y1| (x, y1));
+        for (x1,y1) in [up, Some((x+1,y)), left, Some((x,y+1))].into_iter().flatten() {
             let next_cost = match maze.get(x1).and_then(|row| row.get(y1)) {
                 Some(c) => -cost + c,
                 None => continue,
@@ -1,7 +1,9 @@
 
-use hex;
 use bitvec::prelude::*;
+use crate::answer::Answer;
+use crate::parse::Input;
 
+/// Transmission in the BITS format, decoded into its packets.
 pub struct PacketDecoder {
     // bits: BitVec<u8, Msb0>
     version_sum: usize,
@@ -23,7 +25,7 @@ impl Remainder {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operation {
     Sum,
     Product,
@@ -34,26 +36,27 @@ enum Operation {
     Equal
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Operator {
     operation: Operation,
     remainder: Remainder,
     length: usize
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Number {
     value: usize,
     length: usize
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Packet {
     Op(Operator),
     Num(Number)
 }
 
-fn parse_data(data: &BitSlice<u8, Msb0>) -> (usize, Vec<Packet>) {    
+// Errors with the index of the bit where a packet got cut off
+fn parse_data(data: &BitSlice<u8, Msb0>) -> Result<(usize, Vec<Packet>), usize> {    
     let mut i: usize = 0; // Pointer to the current index
     let mut version_sum: usize = 0;
     let mut equation: Vec<Packet> = vec![];
@@ -66,14 +69,17 @@ fn parse_data(data: &BitSlice<u8, Msb0>) -> (usize, Vec<Packet>) {
         i += 3;
         match type_id.load_be::<u8>() {
             4 => {
-                let start = i.clone();
+                let start = i;
                 let mut value_bits =  bitvec![u8, Msb0;];                
                 for chunk in data[i ..].chunks(5) {
+                    if chunk.len() < 5 {
+                        return Err(i);
+                    }
                     i+= 5;
                     let continues = chunk[0];
                     let value = &chunk[1 .. 5];
                     value_bits.extend_from_bitslice(value);
-                    if continues == false {
+                    if !continues {
                         break;
                     }                        
                 }
@@ -93,6 +99,10 @@ fn parse_data(data: &BitSlice<u8, Msb0>) -> (usize, Vec<Packet>) {
                     _ => panic!("Invalid operator type")
                 };
                 let length_type_id = &data[i];
+                let length_bits = if *length_type_id { 11 } else { 15 };
+                if data.len() < i + 1 + length_bits {
+                    return Err(i);
+                }
                 i += 1;
                 match length_type_id {
                     true => {
@@ -122,117 +132,142 @@ fn parse_data(data: &BitSlice<u8, Msb0>) -> (usize, Vec<Packet>) {
         }            
     }
 
-    (version_sum, equation)
+    Ok((version_sum, equation))
 }
 
 impl crate::Advent for PacketDecoder {
-    fn new(data: &str) -> PacketDecoder {
-        let line = data
-            .lines()
-            .next()
-            .unwrap();
-        let hex = hex::decode(line);              
-        // println!("Hex data: {:?}", hex);
-        let bit_vec = if let Ok(hex) = hex {
-            BitVec::<_, Msb0>::try_from_vec(hex).unwrap()
-        } else {
-            BitVec::new()
-        };
+    fn new(data: &str) -> anyhow::Result<PacketDecoder> {
+        let input = Input::new(data);
+        let line = input.expect(input.lines().next(), data, "a hexadecimal transmission")?;
+        let hex = hex::decode(line).map_err(|err| match err {
+            hex::FromHexError::InvalidHexCharacter { index, .. } => {
+                input.error(&line[index..index + 1], "expected a hexadecimal digit")
+            }
+            err => input.error(line, err),
+        })?;
+        let bit_vec = BitVec::<_, Msb0>::from_vec(hex);
 
         // println!("Bit vec: {:?}", bit_vec);
 
-        let (version_sum, equation) = parse_data(&bit_vec);
+        // Each hexadecimal digit holds 4 bits
+        let (version_sum, equation) = parse_data(&bit_vec)
+            .map_err(|bit| input.error(&line[bit / 4..bit / 4 + 1], "packet ends before it is complete"))?;
         
         // println!("Equation: {:?}", equation);
 
-        PacketDecoder { version_sum, equation }
+        Ok(PacketDecoder { version_sum, equation })
     }
 
-    fn part1(&mut self) -> usize {        
+    fn part1(&mut self) -> Answer {
+        self.version_sum().into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        self.value().into()
+    }
+}
+
+impl PacketDecoder {
+    /// Sum of the version numbers of every packet.
+    pub fn version_sum(&self) -> usize {
         self.version_sum
     }
 
-    fn part2(&mut self) -> usize {                        
-        while self.equation.len() > 1 {   
-            let mut iterator = self.equation.iter().enumerate();        
-
-            let mut all_numbers_index: Option<(usize, usize)> = None;
-            'outer: while let Some((i, packet)) = iterator.next() {            
-                match packet {
-                    Packet::Op(operator) => {
-                        let mut length_sum: usize = 0;
-                        let mut num_subpackets: usize = 0;
-                        let mut child_index: usize = i + 1;
-                        while length_sum < operator.remainder.val() {   
-                            let next = self.equation.get(child_index);
-                            child_index += 1;                            
-                            if let Some(next) = next {
-                                match next {
-                                    Packet::Num(num) => {
-                                        match operator.remainder {
-                                            Remainder::Number(_) => length_sum += 1,
-                                            Remainder::Length(_) => length_sum += num.length                                    
-                                        }                                    
-                                    },
-                                    Packet::Op(_) => { continue 'outer; }
-                                }
-                                num_subpackets += 1;                    
-                            } else {
-                                continue 'outer;
-                            }
-                        }         
-                        all_numbers_index = Some((i, num_subpackets));            
-                        
-                    },
-                    _ => { continue 'outer; }
+    /// Evaluates the expression of the outermost packet.
+    ///
+    /// ```
+    /// use advent_of_code::Advent;
+    /// use advent_of_code::day_16::PacketDecoder;
+    ///
+    /// let decoder = PacketDecoder::new("9C0141080250320F1802104A08").unwrap();
+    /// assert_eq!(decoder.value(), 1);
+    /// ```
+    pub fn value(&self) -> usize {
+        evaluate(self.equation.clone())
+    }
+}
 
-                }
-                if let Some(_) = all_numbers_index {
-                    break 'outer
-                }
-            } 
-            if let Some((all_numbers_index, num_subpackets)) = all_numbers_index {
-                // let mut value: usize = 0;
-                let mut length_sum: usize = 0;
-                let packet = &self.equation[all_numbers_index];
-                let value = match packet {
-                    Packet::Op(operator) => {                        
-                        let numbers: Vec<&Number> = self.equation[all_numbers_index + 1 .. all_numbers_index + 1 + num_subpackets].iter().filter_map(|p| {
-                            match p {
-                                Packet::Num(num) => Some(num),
-                                Packet::Op(_) => None
+// Replaces the innermost operators with the number they evaluate to until one number is left
+fn evaluate(mut equation: Vec<Packet>) -> usize {
+    while equation.len() > 1 {   
+        let iterator = equation.iter().enumerate();        
+
+        let mut all_numbers_index: Option<(usize, usize)> = None;
+        'outer: for (i, packet) in iterator {            
+            match packet {
+                Packet::Op(operator) => {
+                    let mut length_sum: usize = 0;
+                    let mut num_subpackets: usize = 0;
+                    let mut child_index: usize = i + 1;
+                    while length_sum < operator.remainder.val() {   
+                        let next = equation.get(child_index);
+                        child_index += 1;                            
+                        if let Some(next) = next {
+                            match next {
+                                Packet::Num(num) => {
+                                    match operator.remainder {
+                                        Remainder::Number(_) => length_sum += 1,
+                                        Remainder::Length(_) => length_sum += num.length                                    
+                                    }                                    
+                                },
+                                Packet::Op(_) => { continue 'outer; }
                             }
-                        }).collect();
-                        let values: Vec<usize> = numbers.iter().map(|n| n.value).collect();
-                        length_sum += numbers.iter().map(|n| n.length).sum::<usize>();
-                        length_sum += operator.length;
-                        match operator.operation {
-                            Operation::Sum => values.iter().sum(),
-                            Operation::Product => values.iter().product(),
-                            Operation::Minimum => *values.iter().min().unwrap(),
-                            Operation::Maximum => *values.iter().max().unwrap(),
-                            Operation::Greater => if values[0] > values[1] { 1 } else { 0 },
-                            Operation::Less => if values[0] < values[1] { 1 } else { 0 },
-                            Operation::Equal => if values[0] == values[1] { 1 } else { 0 },                        
+                            num_subpackets += 1;                    
+                        } else {
+                            continue 'outer;
                         }
-                    },
-                    _ => panic!("This should be an operator")
-                };     
+                    }         
+                    all_numbers_index = Some((i, num_subpackets));            
+                    
+                },
+                _ => { continue 'outer; }
 
-                let number = Number { value, length: length_sum };
+            }
+            if all_numbers_index.is_some() {
+                break 'outer
+            }
+        } 
+        if let Some((all_numbers_index, num_subpackets)) = all_numbers_index {
+            // let mut value: usize = 0;
+            let mut length_sum: usize = 0;
+            let packet = &equation[all_numbers_index];
+            let value = match packet {
+                Packet::Op(operator) => {                        
+                    let numbers: Vec<&Number> = equation[all_numbers_index + 1 .. all_numbers_index + 1 + num_subpackets].iter().filter_map(|p| {
+                        match p {
+                            Packet::Num(num) => Some(num),
+                            Packet::Op(_) => None
+                        }
+                    }).collect();
+                    let values: Vec<usize> = numbers.iter().map(|n| n.value).collect();
+                    length_sum += numbers.iter().map(|n| n.length).sum::<usize>();
+                    length_sum += operator.length;
+                    match operator.operation {
+                        Operation::Sum => values.iter().sum(),
+                        Operation::Product => values.iter().product(),
+                        Operation::Minimum => *values.iter().min().unwrap(),
+                        Operation::Maximum => *values.iter().max().unwrap(),
+                        Operation::Greater => if values[0] > values[1] { 1 } else { 0 },
+                        Operation::Less => if values[0] < values[1] { 1 } else { 0 },
+                        Operation::Equal => if values[0] == values[1] { 1 } else { 0 },                        
+                    }
+                },
+                _ => panic!("This should be an operator")
+            };     
 
-                let packet = self.equation.get_mut(all_numbers_index).unwrap();
-                *packet = Packet::Num(number);
+            let number = Number { value, length: length_sum };
 
-                self.equation.drain(all_numbers_index + 1 .. all_numbers_index + 1 + num_subpackets);
-            }
-            
+            let packet = equation.get_mut(all_numbers_index).unwrap();
+            *packet = Packet::Num(number);
 
+            equation.drain(all_numbers_index + 1 .. all_numbers_index + 1 + num_subpackets);
         }
-        match &self.equation[0] {
-            Packet::Num(num) => num.value,
-            _ => unreachable!()
-        }
+        
+
+    }
+    match &equation[0] {
+        Packet::Num(num) => num.value,
+        _ => unreachable!()
     }
 }
 
@@ -1,5 +1,7 @@
 use super::euclidean::Coordinate;
 use std::cmp;
+use crate::answer::Answer;
+use crate::parse::Input;
 
 type Coord = Coordinate<i32>;
 
@@ -23,7 +25,7 @@ impl Projectile {
         }
     }
 
-    fn step(&mut self) -> () {
+    fn step(&mut self) {
         self.location.y += self.vector.y;
         self.vector.y -= 1;
         
@@ -66,7 +68,7 @@ impl TrickShot {
     fn min_x(&self) -> i32 {
         let mut i = 0;
         let mut sum = 0;
-        while sum <= self.target.start.x {
+        while sum < self.target.start.x {
             i += 1;
             sum += i;
         }
@@ -86,28 +88,59 @@ impl TrickShot {
     }
     
     fn max_height(&self) -> i32 {
-        (1..self.max_y()).into_iter().sum()
+        // The highest shot passes y = 0 again at the speed of the depth of the target and lands
+        // on its bottom row the step after, which hits if an x velocity runs out above the target by then
+        let depth = self.max_y();
+        let stalls = (1..=2 * depth).any(|vx| (self.target.start.x..=self.target.end.x).contains(&(vx * (vx + 1) / 2)));
+        if stalls {
+            return (1..depth).sum();
+        }
+        self.hits().iter().map(|vector| (1..=vector.y).sum()).max().unwrap_or(0)
+    }
+
+    /// Launch velocities which end up inside the target.
+    fn hits(&self) -> Vec<Coord> {
+        let min_x = self.min_x();
+        let max_x = self.max_x();
+        let (min_x, max_x) = TrickShot::min_max(min_x, max_x);
+        let max_y = self.max_y();
+        let min_y = self.min_y();
+        let (min_y, max_y) = TrickShot::min_max(min_y, max_y);
+        let mut hits = vec![];
+        for vec_x in min_x..=max_x {
+            for vec_y in min_y..=max_y {    
+                let vector = Coord{ x: vec_x, y: vec_y };
+                let mut projectile = Projectile::new( vector.clone() );
+                while !projectile.overshot(&self.target) {
+                    projectile.step();
+                    if projectile.is_inside(&self.target) {
+                        hits.push(vector);
+                        break;
+                    }
+                }
+            }
+        }
+        hits
     }
 }
 
 impl crate::Advent for TrickShot {
-    fn new(data: &str) -> TrickShot {
-        let (xs, ys) = data
-            .strip_prefix("target area: ").unwrap()
-            .split_once(", ").unwrap();
+    fn new(data: &str) -> anyhow::Result<TrickShot> {
+        let input = Input::new(data);
+        let (xs, ys) = input.split_once(input.strip_prefix(data, "target area: ")?, ", ")?;
     
-        let (xs1, xs2) = xs.strip_prefix("x=").unwrap().split_once("..").unwrap();
-        let x1: i32 = xs1.parse().unwrap();
-        let x2: i32 = xs2.parse().unwrap();
+        let (xs1, xs2) = input.split_once(input.strip_prefix(xs, "x=")?, "..")?;
+        let x1: i32 = input.parse(xs1)?;
+        let x2: i32 = input.parse(xs2)?;
 
         let (x1, x2) = TrickShot::min_max(x1, x2);
 
         let x1 = cmp::min(x1, x2);
         let x2 = cmp::max(x1, x2);
 
-        let (ys1, ys2) = ys.strip_prefix("y=").unwrap().split_once("..").unwrap();
-        let y1: i32 = ys1.parse().unwrap();
-        let y2: i32 = ys2.parse().unwrap();
+        let (ys1, ys2) = input.split_once(input.strip_prefix(ys, "y=")?, "..")?;
+        let y1: i32 = input.parse(ys1)?;
+        let y2: i32 = input.parse(ys2)?;
         
         let (y1, y2) = TrickShot::min_max(y1, y2);
 
@@ -116,36 +149,59 @@ impl crate::Advent for TrickShot {
             end: Coordinate { x: x2, y: y2 }
         };
         // println!("Area: {:?}", area);
-        TrickShot { target: area }
+        Ok(TrickShot { target: area })
     }
 
 
 
-    fn part1(&mut self) -> usize {
-        self.max_height() as usize
+    fn part1(&mut self) -> Answer {
+        self.max_height().into()
     }
 
-    fn part2(&mut self) -> usize {
-        let min_x = self.min_x();
-        let max_x = self.max_x();
-        let (min_x, max_x) = TrickShot::min_max(min_x, max_x);
-        let max_y = self.max_y();
-        let min_y = self.min_y();
-        let (min_y, max_y) = TrickShot::min_max(min_y, max_y);
-        let mut counter: usize = 0;
-        for vec_x in min_x..=max_x {
-            for vec_y in min_y..=max_y {    
-                let vector = Coord{ x: vec_x, y: vec_y };
-                let mut projectile = Projectile::new( vector );
-                while !projectile.overshot(&self.target) {
-                    projectile.step();
-                    if projectile.is_inside(&self.target) {
-                        counter += 1;
+    fn part2(&mut self) -> Answer {
+        self.hits().len().into()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+    use proptest::prelude::*;
+
+    /// Highest point of a shot hitting the target and the number of velocities which hit it, simulating every shot.
+    fn every_shot((left, right): (i32, i32), (bottom, top): (i32, i32)) -> (i32, usize) {
+        let (mut highest, mut hits) = (0, 0);
+        for vx in 0..=right {
+            for vy in bottom..=-bottom {
+                let (mut x, mut y, mut dx, mut dy, mut peak) = (0, 0, vx, vy, 0);
+                while x <= right && y >= bottom {
+                    x += dx;
+                    y += dy;
+                    dx -= dx.signum();
+                    dy -= 1;
+                    peak = peak.max(y);
+                    if (left..=right).contains(&x) && (bottom..=top).contains(&y) {
+                        highest = highest.max(peak);
+                        hits += 1;
                         break;
                     }
                 }
             }
         }
-        counter
+        (highest, hits)
     }
-}
\ No newline at end of file
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1000))]
+
+        /// Targets like the puzzle's, ahead of the launcher and below it.
+        #[test]
+        fn matches_every_shot(left in 1..40i32, width in 0..20i32, bottom in -40..-1i32, height in 0..20i32) {
+            let (right, top) = (left + width, (bottom + height).min(-1));
+            let mut shot = TrickShot::new(&format!("target area: x={}..{}, y={}..{}", left, right, bottom, top)).unwrap();
+            let (highest, hits) = every_shot((left, right), (bottom, top));
+            prop_assert_eq!(shot.part1(), highest.into());
+            prop_assert_eq!(shot.part2(), hits.into());
+        }
+    }
+}
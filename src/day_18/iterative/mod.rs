@@ -0,0 +1,129 @@
+// Alternative to the symbol list in `mod.rs`: parses the numbers into trees, then adds and
+// reduces them as flat lists of regular numbers with the depth they are nested at.
+
+mod snailfish_number;
+
+use crate::answer::Answer;
+use crate::parse::{Input, ParseError};
+use snailfish_number::El;
+
+type Flat = Vec<(u32, usize)>;
+
+pub struct Snailfish {
+    numbers: Vec<Flat>
+}
+
+fn flatten(el: &El) -> Flat {
+    el.inorder_iter().map(|(el, depth)| match el {
+        El::Nr(x) => (*x, depth),
+        El::Pr(_) => unreachable!()
+    }).collect()
+}
+
+fn add(lhs: &Flat, rhs: &Flat) -> Flat {
+    let mut sum: Flat = lhs.iter().chain(rhs).map(|&(x, depth)| (x, depth + 1)).collect();
+    reduce(&mut sum);
+    sum
+}
+
+fn reduce(number: &mut Flat) {
+    loop {
+        // Numbers nested in four pairs only ever come in pairs of two regular numbers
+        if let Some(i) = number.iter().position(|&(_, depth)| depth > 4) {
+            let ((lhs, depth), (rhs, _)) = (number[i], number[i + 1]);
+            if i > 0 {
+                number[i - 1].0 += lhs;
+            }
+            if let Some(next) = number.get_mut(i + 2) {
+                next.0 += rhs;
+            }
+            number[i] = (0, depth - 1);
+            number.remove(i + 1);
+        } else if let Some(i) = number.iter().position(|&(x, _)| x >= 10) {
+            let (x, depth) = number[i];
+            number[i] = (x / 2, depth + 1);
+            number.insert(i + 1, (x.div_ceil(2), depth + 1));
+        } else {
+            break;
+        }
+    }
+}
+
+fn magnitude(number: &Flat) -> u32 {
+    let mut number = number.clone();
+    while number.len() > 1 {
+        // The first of the deepest numbers starts a pair of two regular numbers
+        let deepest = number.iter().map(|&(_, depth)| depth).max().unwrap();
+        let i = number.iter().position(|&(_, depth)| depth == deepest).unwrap();
+        number[i] = (3 * number[i].0 + 2 * number[i + 1].0, deepest - 1);
+        number.remove(i + 1);
+    }
+    number[0].0
+}
+
+impl crate::Advent for Snailfish {
+    fn new(data: &str) -> anyhow::Result<Snailfish> {
+        let input = Input::new(data);
+        let numbers: Vec<Flat> = input.lines().map(|l| {
+            El::parse(&input, l).map(|el| flatten(&el))
+        }).collect::<Result<_, ParseError>>()?;
+        if numbers.is_empty() {
+            return Err(input.missing(data, "snailfish numbers").into());
+        }
+        Ok(Snailfish { numbers })
+    }
+
+    fn part1(&mut self) -> Answer {
+        let sum = self.numbers[1..].iter().fold(self.numbers[0].clone(), |sum, number| add(&sum, number));
+        magnitude(&sum).into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        let numbers = &self.numbers;
+        (0..numbers.len())
+            .flat_map(|i| (0..numbers.len()).filter(move |&j| i != j).map(move |j| (i, j)))
+            .map(|(i, j)| magnitude(&add(&numbers[i], &numbers[j])))
+            .max()
+            .unwrap_or(0)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+
+    #[test]
+    fn inorder_iter() {
+        let el = El::from_str("[[[9,[3,8]],[[0,9],6]],[[[3,7],[4,9]],3]]");
+        let items: Vec<_> = el.inorder_iter().collect();
+        assert_eq!(items, vec![
+            (&El::Nr(9), 3),
+            (&El::Nr(3), 4),
+            (&El::Nr(8), 4),
+            (&El::Nr(0), 4),
+            (&El::Nr(9), 4),
+            (&El::Nr(6), 3),
+            (&El::Nr(3), 4),
+            (&El::Nr(7), 4),
+            (&El::Nr(4), 4),
+            (&El::Nr(9), 4),
+            (&El::Nr(3), 2)
+        ])
+    }
+
+    #[test]
+    fn reduces_sums() {
+        let sum = add(&flatten(&El::from_str("[[[[4,3],4],4],[7,[[8,4],9]]]")), &flatten(&El::from_str("[1,1]")));
+        assert_eq!(sum, flatten(&El::from_str("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")));
+        assert_eq!(magnitude(&flatten(&El::from_str("[[1,2],[[3,4],5]]"))), 143);
+    }
+
+    #[test]
+    fn example() {
+        let mut snailfish = Snailfish::new(include_str!("../example.txt")).unwrap();
+        assert_eq!(snailfish.part1(), Answer::Int(4140));
+        assert_eq!(snailfish.part2(), Answer::Int(3993));
+    }
+}
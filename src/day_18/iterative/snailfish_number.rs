@@ -1,5 +1,6 @@
 use std::fmt;
 use std::ops;
+use crate::parse::{self, Input, ParseError};
 
 type ElPtr = Box<El>; 
 
@@ -29,42 +30,33 @@ pub enum El {
 }
 
 impl El {
-    pub fn from_str(s: &str) -> El {
+    pub fn parse<'a>(input: &Input<'a>, s: &'a str) -> Result<El, ParseError> {
         let mut opened: Vec<El> = vec![];
-        s.chars().for_each(|c| {
+        for c in parse::chars(s) {
             match c {
-                '[' => {},
-                ']' => {         
-                    let rhs = opened.pop().unwrap();
-                    let lhs = opened.pop().unwrap();
-
-                    let new_node = Node {
-                        lhs: lhs.into(),
-                        rhs: rhs.into()
+                "[" | " " | "," => {},
+                "]" => {
+                    let (Some(rhs), Some(lhs)) = (opened.pop(), opened.pop()) else {
+                        return Err(input.error(c, "expected two elements before closing a pair"));
                     };
-                    opened.push(El::Pr(new_node));         
+                    opened.push(lhs + rhs);
                 },
-                ' ' => {},
-                ',' => {},
-                x => {
-                    let x = x.to_digit(10).unwrap();
-                    opened.push(El::Nr(x));
-                }
+                x => opened.push(El::Nr(input.parse(x)?)),
             }
-        });
-        opened.pop().unwrap()
-    }
-
-    pub fn inorder_iter(&self) -> InorderTraversal {
-        InorderTraversal::new(self)
+        }
+        match (opened.pop(), opened.is_empty()) {
+            (Some(el), true) => Ok(el),
+            _ => Err(input.error(s, "expected a single snailfish number")),
+        }
     }
 
-    pub fn inorder_iter_mut(&mut self) -> InorderTraversalMut {
-        InorderTraversalMut::new(self)
+    #[cfg(test)]
+    pub fn from_str(s: &str) -> El {
+        El::parse(&Input::new(s), s).unwrap()
     }
 
-    pub fn split(&mut self) -> Option<()> {
-        None
+    pub fn inorder_iter(&self) -> InorderTraversal<'_> {
+        InorderTraversal::new(self)
     }
 }
 
@@ -94,7 +86,7 @@ pub struct InorderTraversal<'a> {
 }
 
 impl<'a> InorderTraversal<'a> {
-    fn new(root: &'a El) -> InorderTraversal{
+    fn new(root: &'a El) -> InorderTraversal<'a> {
         InorderTraversal { current: Some(root), queue: vec![], depth: 0 }
     }
 }
@@ -109,7 +101,7 @@ impl<'a> Iterator for InorderTraversal<'a> {
                     El::Pr(node) => {
                         self.depth += 1;
                         self.queue.push((el, self.depth));
-                        self.current = Some(&node.lhs.as_ref());
+                        self.current = Some(node.lhs.as_ref());
                     },
                     El::Nr(_) => {                        
                         self.current = None;
@@ -121,7 +113,7 @@ impl<'a> Iterator for InorderTraversal<'a> {
                 self.depth = previous_depth;
                 match previous {
                     El::Pr(node) => { 
-                        self.current = Some(&node.rhs.as_ref());
+                        self.current = Some(node.rhs.as_ref());
                     },
                     El::Nr(_) => { unreachable!() }
                 }
@@ -130,31 +122,3 @@ impl<'a> Iterator for InorderTraversal<'a> {
         None
     }
 }
-
-pub struct InorderTraversalMut<'a> {
-    current: &'a mut El,
-    it: InorderTraversal<'a>
-}
-
-impl<'a> InorderTraversalMut<'a> {
-    fn new(root: &'a mut El) -> InorderTraversalMut {
-        InorderTraversalMut { current: root, it: root.inorder_iter() }
-    }
-}
-
-impl<'a> Iterator for InorderTraversalMut<'a> {
-    type Item = &mut El;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.current {
-            El::Nr(_) => None,
-            El::Pr(_) => {
-                let next = self.it.next();
-                if let Some((el, depth)) = next {
-                    return Some(&mut *el)
-                }
-                None
-            }
-        }
-    }
-}
\ No newline at end of file
@@ -1,6 +1,10 @@
 
 use std::ops::ControlFlow;
 use std::fmt;
+use crate::answer::Answer;
+use crate::parse::{self, Input, ParseError};
+
+pub mod iterative;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum SnailSymbol {
@@ -20,12 +24,12 @@ impl SnailSymbol {
         }
     }
 
-    fn to_char(&self) -> char {
+    fn to_char(self) -> char {
         match self {
             SnailSymbol::LBracket => { '[' }
             SnailSymbol::RBracket => { ']' }
             SnailSymbol::Comma => { ',' }
-            SnailSymbol::Num(x) => { std::char::from_digit(*x as u32, 10).unwrap() }
+            SnailSymbol::Num(x) => { std::char::from_digit(x as u32, 10).unwrap() }
         }
     }
 }
@@ -39,7 +43,26 @@ impl fmt::Display for SnailSymbol {
 type SnailfishNumber = Vec<SnailSymbol>;
 
 fn parse_snail(s: &str) -> SnailfishNumber {
-    s.chars().map(|c| SnailSymbol::from_char(c)).collect()
+    s.chars().map(SnailSymbol::from_char).collect()
+}
+
+// Makes sure the line only holds single digits in balanced brackets before it gets parsed
+fn validate_snail(input: &Input, s: &str) -> Result<(), ParseError> {
+    let mut depth: usize = 0;
+    for c in parse::chars(s) {
+        match c {
+            "[" => depth += 1,
+            "]" if depth == 0 => return Err(input.error(c, "closing bracket without an opening one")),
+            "]" => depth -= 1,
+            "," => (),
+            c if c.len() == 1 && c.as_bytes()[0].is_ascii_digit() => (),
+            c => return Err(input.error(c, "expected a bracket, comma or digit")),
+        }
+    }
+    if depth > 0 {
+        return Err(input.missing(s, "a closing bracket"));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -109,21 +132,25 @@ fn explode(number: &mut SnailfishNumber) -> ControlFlow<()> {
             _ => unreachable!("Invalid format")        
         };
         
-        number[..explode_pos].iter_mut().rev().find_map(|s| {
+        if let Some(s) = number[..explode_pos].iter_mut().rev().find_map(|s| {
             if let SnailSymbol::Num(s) = s {
                 Some(s)
             } else {
                 None
             }
-        }).map(|s| *s += lhs);
+        }) {
+            *s += lhs;
+        }
 
-        number[explode_pos + 4 ..].iter_mut().find_map(|s| {
+        if let Some(s) = number[explode_pos + 4 ..].iter_mut().find_map(|s| {
             if let SnailSymbol::Num(s) = s {
                 Some(s)
             } else {
                 None
             }
-        }).map(|s| *s += rhs);
+        }) {
+            *s += rhs;
+        }
 
         number[explode_pos] = SnailSymbol::Num(0);
         number.drain(explode_pos + 1 .. explode_pos + 5);
@@ -195,15 +222,20 @@ pub struct Snailfish {
 }
 
 impl crate::Advent for Snailfish {
-    fn new(data: &str) -> Snailfish {
-        let numbers: Vec<SnailfishNumber> = data.lines().map(|l| {
-            parse_snail(l)
-        }).collect();
+    fn new(data: &str) -> anyhow::Result<Snailfish> {
+        let input = Input::new(data);
+        let numbers: Vec<SnailfishNumber> = input.lines().map(|l| {
+            validate_snail(&input, l)?;
+            Ok(parse_snail(l))
+        }).collect::<Result<_, ParseError>>()?;
+        if numbers.is_empty() {
+            return Err(input.missing(data, "snailfish numbers").into());
+        }
 
-        Snailfish { numbers }
+        Ok(Snailfish { numbers })
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         let mut current_number = self.numbers[0].clone();
         let mut temp = vec![];
 
@@ -214,13 +246,13 @@ impl crate::Advent for Snailfish {
             reduce(&mut current_number);            
         }
 
-        magnitude(&current_number)
+        magnitude(&current_number).into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let mut temp = vec![];
         self.numbers
-            .iter().enumerate().map(|(lidx, lhs)| {
+            .iter().enumerate().flat_map(|(lidx, lhs)| {
                 self.numbers.iter().enumerate().filter_map(move |(ridx, rhs)| {
                     if lidx != ridx {
                         Some((lhs, rhs))
@@ -228,12 +260,12 @@ impl crate::Advent for Snailfish {
                         None
                     }
                 })
-            }).flatten().map(|(lhs, rhs)| {
+            }).map(|(lhs, rhs)| {
                 temp.clear();
                 add_snailnum(lhs, rhs, &mut temp);
                 reduce(&mut temp);
                 magnitude(&temp)
-            }).max().unwrap()            
+            }).max().unwrap().into()            
     }
 }
 
@@ -272,27 +304,27 @@ mod tests {
     fn test_explode() {
         let mut number = parse_snail("[[[[[9,8],1],2],3],4]");
         let result = parse_snail("[[[[0,9],2],3],4]");
-        explode(&mut number);
+        let _ = explode(&mut number);
         assert_eq!(number , result);
 
         let mut number = parse_snail("[7,[6,[5,[4,[3,2]]]]]");
         let result = parse_snail("[7,[6,[5,[7,0]]]]");
-        explode(&mut number);
+        let _ = explode(&mut number);
         assert_eq!(number, result);
 
         let mut number = parse_snail("[[6,[5,[4,[3,2]]]],1]");
         let result = parse_snail("[[6,[5,[7,0]]],3]");
-        explode(&mut number);
+        let _ = explode(&mut number);
         assert_eq!(number, result);
 
         let mut number = parse_snail("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]");
         let result = parse_snail("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]");
-        explode(&mut number);
+        let _ = explode(&mut number);
         assert_eq!(number, result);
 
         let mut number = parse_snail("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]");
         let result = parse_snail("[[3,[2,[8,0]]],[9,[5,[7,0]]]]");
-        explode(&mut number);
+        let _ = explode(&mut number);
         assert_eq!(number, result);
     }
 
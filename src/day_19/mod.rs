@@ -7,6 +7,10 @@ use std::ops::{Add, Sub, AddAssign};
 use std::collections::{HashMap, VecDeque};
 
 use itertools::Itertools;
+use crate::answer::Answer;
+use crate::parse::{Input, ParseError};
+
+pub mod nightly;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum Sign {
@@ -38,7 +42,7 @@ impl Axis {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct Coord {
     x: isize, 
     y: isize,
@@ -73,11 +77,6 @@ impl fmt::Display for Coord {
     }
 }
 
-impl PartialEq for Coord {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && self.z == other.z
-    }
-}
 
 
 #[derive(Clone)]
@@ -91,29 +90,28 @@ struct Scanner {
 }
 
 impl Scanner {
-    fn from_str(s: &str) -> Self {
-        let mut it = s.lines();
-
-        let head = it.next().unwrap();
-        let i: usize = head.strip_prefix("--- scanner ").unwrap().strip_suffix(" ---").unwrap().parse().unwrap();
-
-        let beacons: Vec<Coord> = it.map(|l| {
-            let coords: Vec<isize> = l.split(",").map(|s| {
-                let r = s.parse();
-                if r.is_err() {
-                    println!("S: {}, L: {}", s, l);
-
-                }
-                r.unwrap()
-            }).collect();
-            Coord {
-                x: coords[0],
-                y: coords[1],
-                z: coords[2]
+    fn parse<'a>(input: &Input<'a>, lines: &[&'a str]) -> Result<Self, ParseError> {
+        let head = lines[0];
+        let i: usize = input.parse(
+            head.strip_prefix("--- scanner ")
+                .and_then(|h| h.strip_suffix(" ---"))
+                .ok_or_else(|| input.error(head, "expected a '--- scanner N ---' header"))?
+        )?;
+
+        let beacons: Vec<Coord> = lines[1..].iter().map(|l| {
+            let coords: Vec<isize> = l.split(',').map(|s| input.parse(s)).collect::<Result<_, _>>()?;
+            match coords[..] {
+                [x, y, z] => Ok(Coord { x, y, z }),
+                _ => Err(input.error(l, "expected three coordinates")),
             }
-        
-        }).collect();
-        Scanner::new( beacons, i )         
+        }).collect::<Result<_, _>>()?;
+        Ok(Scanner::new( beacons, i ))
+    }
+
+    #[cfg(test)]
+    fn from_str(s: &str) -> Self {
+        let lines: Vec<&str> = s.lines().collect();
+        Scanner::parse(&Input::new(s), &lines).unwrap()
     }
 
     fn new(beacons: Vec<Coord>, i: usize) -> Self {
@@ -155,17 +153,17 @@ impl Scanner {
                 Axis::X => { 
                     let temp = c.y;
                     c.y = c.z;
-                    c.z = temp * -1;
+                    c.z = -temp;
                 },
                 Axis::Y => {
                     let temp = c.z;
                     c.z = c.x;
-                    c.x = temp * -1;
+                    c.x = -temp;
                 },
                 Axis::Z => { 
                     let temp = c.x;
                     c.x = c.y;
-                    c.y = temp * -1;
+                    c.y = -temp;
                 }
             }            
         }
@@ -178,17 +176,17 @@ impl Scanner {
                 Axis::X => { 
                     let temp = c.z;
                     c.z = c.y;
-                    c.y = temp * -1;
+                    c.y = -temp;
                 },
                 Axis::Y => {
                     let temp = c.x;
                     c.x = c.z;
-                    c.z = temp * -1;
+                    c.z = -temp;
                 },
                 Axis::Z => { 
                     let temp = c.y;
                     c.y = c.x;
-                    c.x = temp * -1;
+                    c.x = -temp;
                 }
             }            
         }
@@ -200,12 +198,12 @@ impl Scanner {
             self.rotation_index += 1;
             return;
         }        
-        if self.rotation_index % 4 == 0 {                 
+        if self.rotation_index.is_multiple_of(4) {                 
             // Tilt back to original position
-            self.tilt(self.facing_axis.clone());
+            self.tilt(self.facing_axis);
             self.change_sign();
 
-            if self.rotation_index % 8 == 0 {
+            if self.rotation_index.is_multiple_of(8) {
                 self.change_axis();
                 if self.rotation_index == 24 {
                     self.tilt(Axis::X);
@@ -219,7 +217,7 @@ impl Scanner {
             self.rotation_index += 1;
             return;
         }
-        self.tilt(self.facing_axis.clone());
+        self.tilt(self.facing_axis);
         self.rotation_index += 1;
     }
 
@@ -257,58 +255,75 @@ impl Scanner {
 
 impl fmt::Display for Scanner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Scanner {}:\n", self.i)?;
+        writeln!(f, "Scanner {}:", self.i)?;
         for beacon in &self.beacons {
-            write!(f, "\t{}\n", beacon)?;
+            writeln!(f, "\t{}", beacon)?;
         }
-        write!(f, "\n")        
+        writeln!(f)        
     }
 }
 
 pub struct BeaconScaner {
     scanners: Vec<Scanner>,
-    offsets: Vec<Coord>
+    // Number of beacons and the scanner offsets, shared by both parts
+    located: Option<(usize, Vec<Coord>)>
 }
 
-impl crate::Advent for BeaconScaner {
-    fn new(data: &str) -> Self {
-        let data = data.replace("\r", "");
-        let scanners: Vec<Scanner> = data
-            .split("\n\n")
-            .map(|s| Scanner::from_str(s))
-            .collect();
-        BeaconScaner { scanners, offsets: vec![] }
+impl BeaconScaner {
+    fn locate(&mut self) -> &(usize, Vec<Coord>) {
+        let scanners = &self.scanners;
+        self.located.get_or_insert_with(|| {
+            let mut scanners = VecDeque::from(scanners.clone());
+            let mut offsets = vec![];
+
+            let mut origin = scanners.pop_front().unwrap();
+            origin.c = Some(Coord{ x: 0, y: 0, z: 0});
+            while !scanners.is_empty() {
+                let s2 = scanners.pop_front().unwrap();
+                let mut s2 = s2.clone();
+                let offset = origin.find_offset(&mut s2);
+                if let Some(offset) = offset {
+                    // println!("Matches {}", s2.i);
+                    origin.merge(&mut s2, offset);
+                    offsets.push(offset);
+                } else {
+                    scanners.push_back(s2);
+                }
+            }
+
+            (origin.beacons.len(), offsets)
+        })
     }
+}
 
-    fn part1(&mut self) -> usize {        
-        let mut scanners = VecDeque::from(self.scanners.clone());
-
-        let mut origin = scanners.pop_front().unwrap();
-        origin.c = Some(Coord{ x: 0, y: 0, z: 0});
-        while scanners.len() != 0 {
-            let s2 = scanners.pop_front().unwrap();
-            let mut s2 = s2.clone();
-            let offset = origin.find_offset(&mut s2);
-            if let Some(offset) = offset {
-                // println!("Matches {}", s2.i);
-                origin.merge(&mut s2, offset);
-                self.offsets.push(offset);
-            } else {
-                scanners.push_back(s2);
-            }
+impl crate::Advent for BeaconScaner {
+    fn new(data: &str) -> anyhow::Result<Self> {
+        let input = Input::new(data);
+        let lines: Vec<&str> = input.lines().collect();
+        let scanners: Vec<Scanner> = lines
+            .split(|l| l.is_empty())
+            .filter(|block| !block.is_empty())
+            .map(|block| Scanner::parse(&input, block))
+            .collect::<Result<_, _>>()?;
+        if scanners.is_empty() {
+            return Err(input.missing(data, "scanner reports").into());
         }
+        Ok(BeaconScaner { scanners, located: None })
+    }
 
-        origin.beacons.len()    
+    fn part1(&mut self) -> Answer {        
+        self.locate().0.into()
     }
 
-    fn part2(&mut self) -> usize {
-        let max_dist = self.offsets.iter().permutations(2).map(|v| {
+    fn part2(&mut self) -> Answer {
+        let (_, offsets) = self.locate();
+        let max_dist = offsets.iter().permutations(2).map(|v| {
             let (a, b) = (v[0], v[1]);
             let diff = *a - *b;
             diff.x.abs() + diff.y.abs() + diff.z.abs()
         }).max();
 
-        max_dist.unwrap() as usize
+        max_dist.unwrap().into()
     }
 }
 
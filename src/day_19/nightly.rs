@@ -1,9 +1,14 @@
+// Alternative to the brute force in `mod.rs`: matches scanners by the normalized distances
+// between pairs of their beacons. Written for nightly, now builds on stable.
+
 use std::fmt;
 use std::ops::{Add, Sub, AddAssign};
 use std::cmp;
 use itertools::Itertools;
+use crate::answer::Answer;
+use crate::parse::{Input, ParseError};
 
-#[derive(Clone, Copy, Debug, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct Coord {
     x: isize, 
     y: isize,
@@ -70,12 +75,6 @@ impl fmt::Display for Coord {
     }
 }
 
-impl PartialEq for Coord {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && self.z == other.z
-    }
-}
-
 impl cmp::PartialOrd for Coord {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
@@ -90,12 +89,12 @@ impl cmp::Ord for Coord {
                 let y_ord = self.y.cmp(&other.y);
                 match y_ord {
                     cmp::Ordering::Equal => {
-                        return self.z.cmp(&other.z);
+                        self.z.cmp(&other.z)
                     },
-                    _ => return y_ord
+                    _ => y_ord
                 }
             },
-            _ => return x_ord
+            _ => x_ord
         }
     }
 }
@@ -107,29 +106,22 @@ struct Scanner {
 }
 
 impl Scanner {
-    fn from_str(s: &str) -> Self {
-        let mut it = s.lines();
-
-        let head = it.next().unwrap();
-        let i: usize = head.strip_prefix("--- scanner ").unwrap().strip_suffix(" ---").unwrap().parse().unwrap();
-
-        let beacons: Vec<Coord> = it.map(|l| {
-            let coords: Vec<isize> = l.split(",").map(|s| {
-                let r = s.parse();
-                if r.is_err() {
-                    println!("S: {}, L: {}", s, l);
-
-                }
-                r.unwrap()
-            }).collect();
-            Coord {
-                x: coords[0],
-                y: coords[1],
-                z: coords[2]
+    fn parse<'a>(input: &Input<'a>, lines: &[&'a str]) -> Result<Self, ParseError> {
+        let head = lines[0];
+        let i: usize = input.parse(
+            head.strip_prefix("--- scanner ")
+                .and_then(|h| h.strip_suffix(" ---"))
+                .ok_or_else(|| input.error(head, "expected a '--- scanner N ---' header"))?
+        )?;
+
+        let beacons: Vec<Coord> = lines[1..].iter().map(|l| {
+            let coords: Vec<isize> = l.split(',').map(|s| input.parse(s)).collect::<Result<_, _>>()?;
+            match coords[..] {
+                [x, y, z] => Ok(Coord { x, y, z }),
+                _ => Err(input.error(l, "expected three coordinates")),
             }
-        
-        }).collect();
-        Scanner::new( beacons, i )         
+        }).collect::<Result<_, _>>()?;
+        Ok(Scanner::new( beacons, i ))
     }
 
     fn new(beacons: Vec<Coord>, i: usize) -> Self {
@@ -140,45 +132,45 @@ impl Scanner {
 
 impl fmt::Display for Scanner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Scanner {}:\n", self.i)?;
+        writeln!(f, "Scanner {}:", self.i)?;
         for beacon in &self.beacons {
-            write!(f, "\t{}\n", beacon)?;
+            writeln!(f, "\t{}", beacon)?;
         }
-        write!(f, "\n")        
+        writeln!(f)
     }
 }
 
 fn find_valid_transformer(from: Coord, to: Coord) -> (usize, &'static dyn Fn(Coord) -> Coord) {
     let transformation: [&dyn Fn(Coord) -> Coord; 24] = [
-        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: c.x, y: c.y, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: -c.y, y: c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: c.y, y: -c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: -c.x, y: -c.y, z: c.z}}.into(),
-
-        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: c.x, y: c.y, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: -c.y, y: c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: c.y, y: -c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: -c.x, y: -c.y, z: c.z}}.into(),
-
-        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: c.x, y: c.y, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: -c.y, y: c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: c.y, y: -c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: -c.x, y: -c.y, z: c.z}}.into(),     
-
-        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: c.x, y: c.y, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: -c.y, y: c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: c.y, y: -c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: -c.x, y: -c.y, z: c.z}}.into(),
-
-        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: c.x, y: c.y, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: -c.y, y: c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: c.y, y: -c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: -c.x, y: -c.y, z: c.z}}.into(),
-
-        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: c.x, y: c.y, z: c.z}}.into(),
-        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: -c.y, y: c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: c.y, y: -c.x, z: c.z}}.into(),
-        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: -c.x, y: -c.y, z: c.z}}.into(),
+        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: c.x, y: c.y, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: -c.y, y: c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: c.y, y: -c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: c.y, z: c.z}; Coord { x: -c.x, y: -c.y, z: c.z}},
+
+        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: c.x, y: c.y, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: -c.y, y: c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: c.y, y: -c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: -c.z, z: c.y}; Coord { x: -c.x, y: -c.y, z: c.z}},
+
+        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: c.x, y: c.y, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: -c.y, y: c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: c.y, y: -c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: c.z, z: -c.y}; Coord { x: -c.x, y: -c.y, z: c.z}},     
+
+        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: c.x, y: c.y, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: -c.y, y: c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: c.y, y: -c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.x, y: -c.y, z: -c.z}; Coord { x: -c.x, y: -c.y, z: c.z}},
+
+        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: c.x, y: c.y, z: c.z}},
+        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: -c.y, y: c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: c.y, y: -c.x, z: c.z}},
+        &|c| { let c = Coord { x: c.z, y: c.y, z: -c.x}; Coord { x: -c.x, y: -c.y, z: c.z}},
+
+        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: c.x, y: c.y, z: c.z}},
+        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: -c.y, y: c.x, z: c.z}},
+        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: c.y, y: -c.x, z: c.z}},
+        &|c| { let c = Coord { x: -c.z, y: c.y, z: c.x}; Coord { x: -c.x, y: -c.y, z: c.z}},
     ];
     for (idx, tran) in transformation.iter().copied().enumerate() {
         if tran(from) == to {
@@ -189,8 +181,11 @@ fn find_valid_transformer(from: Coord, to: Coord) -> (usize, &'static dyn Fn(Coo
     unreachable!()
 }
 
+// Distance between two beacons, normalized so it's the same from every side, with the beacons
+type Norm = (Coord, Coord, Coord);
+
 fn solve(scanners: &[Scanner]) -> (Vec<(usize, Coord)>, Vec<Coord>) {
-    let mut normalized: Vec<(usize, Vec<(Coord, Coord, Coord)>)> = scanners
+    let mut normalized: Vec<(usize, Vec<Norm>)> = scanners
         .iter()
         .enumerate()
         .map(|(sidx, s)| {
@@ -219,16 +214,16 @@ fn solve(scanners: &[Scanner]) -> (Vec<(usize, Coord)>, Vec<Coord>) {
         active_normalized.extend(full_normalized.iter().copied());
         active_normalized.sort_unstable();
 
-        normalized.drain_filter(|(nidx, n)|{
+        normalized.extract_if(.., |(nidx, n)| {
             candidate_count.clear();
-            let mut lhsiter = active_normalized.group_by(|lhs, rhs| {lhs.0 == rhs.0}).peekable();
-            let mut rhsiter = n.group_by(|lhs, rhs| {lhs.0 == rhs.0}).peekable();            
+            let mut lhsiter = active_normalized.chunk_by(|lhs, rhs| {lhs.0 == rhs.0}).peekable();
+            let mut rhsiter = n.chunk_by(|lhs, rhs| {lhs.0 == rhs.0}).peekable();            
             while lhsiter.peek().is_some() && rhsiter.peek().is_some() {
                 match (lhsiter.peek(), rhsiter.peek()) {
                     (Some(lhs), Some(rhs)) => {
                         match lhs[0].0.cmp(&rhs[0].0) {
                             std::cmp::Ordering::Equal => {
-                                for (lhs, rhs) in lhs.iter().map(|lhs| rhs.iter().map(move |rhs| (lhs, rhs))).flatten() {
+                                for (lhs, rhs) in lhs.iter().flat_map(|lhs| rhs.iter().map(move |rhs| (lhs, rhs))) {
                                     let left = lhs.2 - lhs.1;
                                     let right = rhs.2 - rhs.1;
                                     let (tranid, tran) = find_valid_transformer(right, left);
@@ -253,7 +248,7 @@ fn solve(scanners: &[Scanner]) -> (Vec<(usize, Coord)>, Vec<Coord>) {
             }
 
             candidate_count.sort_unstable_by_key(|(offset, tranid, _tran)| (*offset, *tranid));
-            let transinfo = candidate_count.group_by(|lhs, rhs| (lhs.0, lhs.1) == (rhs.0, rhs.1))
+            let transinfo = candidate_count.chunk_by(|lhs, rhs| (lhs.0, lhs.1) == (rhs.0, rhs.1))
                 .filter(|candidate| candidate.len() >= 6 )
                 .max_by_key(|candidate| candidate.len());
             let candidate = match transinfo {
@@ -286,32 +281,38 @@ pub struct BeaconScaner {
 }
 
 impl crate::Advent for BeaconScaner {
-    fn new(data: &str) -> Self {
-        let data = data.replace("\r", "");
-        let scanners: Vec<Scanner> = data
-            .split("\n\n")
-            .map(|s| Scanner::from_str(s))
-            .collect();
-        BeaconScaner { scanners }
+    fn new(data: &str) -> anyhow::Result<Self> {
+        let input = Input::new(data);
+        let lines: Vec<&str> = input.lines().collect();
+        let scanners: Vec<Scanner> = lines
+            .split(|l| l.is_empty())
+            .filter(|block| !block.is_empty())
+            .map(|block| Scanner::parse(&input, block))
+            .collect::<Result<_, _>>()?;
+        if scanners.is_empty() {
+            return Err(input.missing(data, "scanner reports").into());
+        }
+        Ok(BeaconScaner { scanners })
     }
 
-    fn part1(&mut self) -> usize {        
+    fn part1(&mut self) -> Answer {
         let (_, mut beacons) = solve(&self.scanners);
         beacons.sort_unstable();
         beacons.dedup();
-        beacons.len()
+        beacons.len().into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let (scanners, _) = solve(&self.scanners);
         scanners.iter()
-            .map(|lhs| {
+            .flat_map(|lhs| {
                 scanners.iter().map(move |rhs| (lhs, rhs))
-            }).flatten()
+            })
             .map(|((_, lhs), (_, rhs))| {
                 let diff = *lhs - *rhs;
                 (diff.x.abs() + diff.y.abs() + diff.z.abs()) as usize
             })
             .max().unwrap()
+            .into()
     }
 }
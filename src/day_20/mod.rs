@@ -1,6 +1,11 @@
 use bitvec::prelude::*;
 use std::fmt;
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::parse::{self, Input, ParseError};
+use crate::simulation::Simulation;
 
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Img {
     map: Vec<BitVec<u8, Msb0>>,
     edge: bool
@@ -8,7 +13,7 @@ struct Img {
 
 impl fmt::Display for Img {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Image: \n")?;
+        writeln!(f, "Image: ")?;
         for bits in &self.map {
             let bits_str: String = bits.iter().map(|c| {
                 match *c {
@@ -16,9 +21,9 @@ impl fmt::Display for Img {
                     false => '⬛',
                 }
             }).collect();
-            write!(f, "{}\n", bits_str)?;
+            writeln!(f, "{}", bits_str)?;
         }
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
@@ -73,9 +78,9 @@ impl Img {
             new_map.push(new_row);
 
             // Reset the view
-            for y in 0..3 {
-                view[y].pop_front();
-                view[y].push_back(self.edge);
+            for row in view.iter_mut() {
+                row.pop_front();
+                row.push_back(self.edge);
             }
         }
 
@@ -83,65 +88,107 @@ impl Img {
     }
 
 }
+pub const PARAMS: &[Param] = &[Param { name: "passes1", default: 2, min: 0 }, Param { name: "passes2", default: 50, min: 0 }];
+
 pub struct TrenchMap {
     img_enhancment_algorithem: BitVec<u8, Msb0>,
-    input_img: Img
+    input_img: Img,
+    passes: [usize; 2],
 }
 
 impl crate::Advent for TrenchMap {
-    fn new(data: &str) -> TrenchMap {
-        let mut lines = data.lines();
-        let img_enhancment_algorithem = lines
-            .next().unwrap()
-            .chars()
-            .map(|c| {
+    fn new(data: &str) -> anyhow::Result<TrenchMap> {
+        let input = Input::new(data);
+        let pixels = |line: &str| -> Result<BitVec<u8, Msb0>, ParseError> {
+            parse::chars(line).map(|c| {
                 match c {
-                    '#' => true,
-                    '.' => false,
-                    _ => panic!("Invalid char {}", c)
+                    "#" => Ok(true),
+                    "." => Ok(false),
+                    _ => Err(input.error(c, "expected '#' or '.'"))
                 }
-            }).collect();
+            }).collect()
+        };
 
-        lines.next();
+        let mut lines = input.lines();
+        let algorithm_line = input.expect(lines.next(), data, "an image enhancement algorithm")?;
+        let img_enhancment_algorithem = pixels(algorithm_line)?;
+        if img_enhancment_algorithem.len() != 512 {
+            return Err(input.error(algorithm_line, "expected an algorithm of 512 pixels").into());
+        }
 
-        let mut input_img: Vec<BitVec<u8, Msb0>> = vec![];
-        data.lines().skip(2).for_each(|l| {
-            let mut bits = bitvec![u8, Msb0;];
-            for c in l.chars() {
-                match c {
-                    '#' => bits.push(true),
-                    '.' => bits.push(false),
-                    _ => panic!("Invalid bit: {}", c)
-                }
-            }
-            input_img.push(bits);
-        });
+        match lines.next() {
+            Some("") => (),
+            Some(line) => return Err(input.error(line, "expected an empty line after the algorithm").into()),
+            None => return Err(input.missing(algorithm_line, "an input image").into()),
+        }
+
+        let input_img: Vec<BitVec<u8, Msb0>> = lines.map(pixels).collect::<Result<_, _>>()?;
         let input_img = Img { map: input_img, edge: false };
         // println!("{}", input_img);
-        TrenchMap { 
+        Ok(TrenchMap { 
             img_enhancment_algorithem,
-            input_img
+            input_img,
+            passes: [PARAMS[0].default, PARAMS[1].default],
+        })
+    }
+
+    fn part1(&mut self) -> Answer {
+        self.lit_after(self.passes[0]).into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        self.lit_after(self.passes[1]).into()
+    }
+
+    fn visualization(&mut self) -> Option<&mut dyn crate::Visualize> {
+        Some(self)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: usize) {
+        match name {
+            "passes1" => self.passes[0] = value,
+            _ => self.passes[1] = value,
         }
     }
+}
+
+/// Image enhanced by the algorithm at every step.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Enhancement<'a> {
+    algorithm: &'a BitVec<u8, Msb0>,
+    img: Img,
+}
+
+impl Simulation for Enhancement<'_> {
+    fn step(&mut self) {
+        self.img = self.img.enhance(self.algorithm);
+    }
+}
+
+impl TrenchMap {
+    fn enhancement(&self) -> Enhancement<'_> {
+        Enhancement { algorithm: &self.img_enhancment_algorithem, img: self.input_img.clone() }
+    }
 
-    fn part1(&mut self) -> usize {
-        let img = self.input_img.enhance(&self.img_enhancment_algorithem);        
-        let img = img.enhance(&self.img_enhancment_algorithem);
-        // println!("{}", img);
-        img.map.iter().flatten().filter(|bit| {
-            **bit
-        }).count()
+    fn lit_after(&self, passes: usize) -> usize {
+        let mut enhancement = self.enhancement();
+        enhancement.run(passes as u64);
+        enhancement.img.map.iter().flatten().filter(|bit| **bit).count()
     }
+}
 
-    fn part2(&mut self) -> usize {
-        let mut img = self.input_img.enhance(&self.img_enhancment_algorithem); 
-        for _ in 1..50 {
-            img = img.enhance(&self.img_enhancment_algorithem);
-        }               
-        // println!("{}", img);
-        img.map.iter().flatten().filter(|bit| {
-            **bit
-        }).count()
+impl crate::Visualize for TrenchMap {
+    fn visualize(&mut self, part: u8, frame: &mut dyn FnMut(String)) {
+        let mut enhancement = self.enhancement();
+        frame(enhancement.img.to_string());
+        for _ in 0..self.passes[part as usize - 1] {
+            enhancement.step();
+            frame(enhancement.img.to_string());
+        }
     }
 }
 
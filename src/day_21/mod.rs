@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::parse::Input;
 
 #[derive(PartialEq, Clone, Debug, Eq, Hash)]
 struct Player {
@@ -29,29 +32,34 @@ impl Player {
 type CacheType = HashMap<(Player, Player), (usize, usize)>;
 
 #[inline]
-fn quantum_play(cache: &mut CacheType, outcome_occurance: &[(usize, usize)], current_player: Player, waiting_player: Player) -> (usize, usize) {
-    if current_player.score >= 21 {
+fn quantum_play(cache: &mut CacheType, outcome_occurance: &[(usize, usize)], goal: usize, current_player: Player, waiting_player: Player) -> (usize, usize) {
+    if current_player.score >= goal {
         return (1, 0);
     }
 
-    if waiting_player.score >= 21 {
+    if waiting_player.score >= goal {
         return (0, 1);
     }
     let cached_value = cache.get(&(current_player.clone(), waiting_player.clone()));
     if let Some(cached_value) = cached_value {
-        return cached_value.clone();
+        return *cached_value;
     }
     let mut answer = (0, 0);
     for (outcome, occurance) in outcome_occurance {
         let mut current_player_copy = current_player.clone();
-        current_player_copy.update(*outcome as usize);
+        current_player_copy.update(*outcome );
         let (x1, y1) = quantum_play(
             cache, 
             outcome_occurance,
+            goal,
             waiting_player.clone(),
             current_player_copy
         );
-        answer = (answer.0 + y1 * occurance, answer.1 + x1 * occurance)
+        // Higher goals than the puzzle's split into more universes than fit a usize
+        let add = |wins: usize, universes: usize| {
+            universes.checked_mul(*occurance).and_then(|u| wins.checked_add(u)).expect("number of universes overflows usize")
+        };
+        answer = (add(answer.0, y1), add(answer.1, x1))
     }
     cache.insert((current_player, waiting_player), answer);
     answer
@@ -68,25 +76,35 @@ impl Dice {
     }
 }
 
+pub const PARAMS: &[Param] = &[Param { name: "goal1", default: 1000, min: 0 }, Param { name: "goal2", default: 21, min: 0 }];
+
 pub struct DiracDice {
-    positions: Vec<usize>
+    positions: Vec<usize>,
+    goals: [usize; 2],
 }
 
 
 impl crate::Advent for DiracDice {
-    fn new(data: &str) -> DiracDice {
-        let positions: Vec<usize> = data.lines().map(|l| {
-            let c = l.chars().last().unwrap();
-            c.to_digit(10).unwrap() as usize      
-        }).collect();        
-        DiracDice { positions }
+    fn new(data: &str) -> anyhow::Result<DiracDice> {
+        let input = Input::new(data);
+        let positions: Vec<usize> = input.lines().map(|l| {
+            let (_, position) = input.split_once(l, ": ")?;
+            match input.parse(position)? {
+                position @ 1..=10 => Ok(position),
+                _ => Err(input.error(position, "expected a position between 1 and 10")),
+            }
+        }).collect::<Result<_, _>>()?;        
+        if positions.len() != 2 {
+            return Err(input.missing(data, "starting positions of exactly two players").into());
+        }
+        Ok(DiracDice { positions, goals: [PARAMS[0].default, PARAMS[1].default] })
     }
 
-    fn part1(&mut self) -> usize {        
+    fn part1(&mut self) -> Answer {        
         let p1 = Player::new(self.positions[0]);
         let p2 = Player::new(self.positions[1]);
         let mut dice = Dice { throws: 0 };
-        let goal: usize = 1000;
+        let goal = self.goals[0];
         let mut result: Option<(Player, Player)> = None;
         let (mut current_player, mut waiting_player) = (p1, p2);
         while result.is_none() {            
@@ -102,10 +120,10 @@ impl crate::Advent for DiracDice {
         }
         
         let result = result.unwrap();
-        result.1.score * dice.throws    
+        (result.1.score * dice.throws).into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let outcome_occurance: Vec<(usize, usize)> = (1..=3usize)
             .cartesian_product(1..=3usize)
             .cartesian_product(1..=3usize)
@@ -117,8 +135,19 @@ impl crate::Advent for DiracDice {
         let mut cache: CacheType = HashMap::new();
         let p1 = Player::new(self.positions[0]);
         let p2 = Player::new(self.positions[1]);
-        let answer = quantum_play(&mut cache, &outcome_occurance, p1, p2);
+        let answer = quantum_play(&mut cache, &outcome_occurance, self.goals[1], p1, p2);
 
-        std::cmp::max(answer.0, answer.1)
+        std::cmp::max(answer.0, answer.1).into()
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, name: &str, value: usize) {
+        match name {
+            "goal1" => self.goals[0] = value,
+            _ => self.goals[1] = value,
+        }
     }
 }
\ No newline at end of file
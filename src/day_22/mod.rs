@@ -2,6 +2,9 @@ use std::collections::VecDeque;
 use std::iter::zip;
 use std::ops::RangeInclusive;
 use std::cmp::Ordering;
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::parse::{Input, ParseError};
 
 #[derive(PartialEq, Debug)]
 enum Union {
@@ -11,30 +14,47 @@ enum Union {
     IsContained,
 }
 
+/// Block of cubes spanning the inclusive x, y and z ranges.
 #[derive(Debug, Clone, PartialEq)]
-struct Cuboid {
+pub struct Cuboid {
     ranges: [RangeInclusive<i64>; 3],
 }
 
 impl Cuboid {
-    fn from_str(s: &str) -> Self {
-        let ranges: Vec<RangeInclusive<i64>> = s
-            .split(",")
-            .map(|r| {
-                let (_coord, range) = r.split_once("=").unwrap();
-                let (from, to) = range.split_once("..").unwrap();
-                let from: i64 = from.parse().unwrap();
-                let to: i64 = to.parse().unwrap();
-
-                std::cmp::min(from, to)..=std::cmp::max(from, to)
-            })
-            .collect();
-        Cuboid {
-            ranges: ranges.try_into().unwrap(),
+    pub fn new(x: RangeInclusive<i64>, y: RangeInclusive<i64>, z: RangeInclusive<i64>) -> Self {
+        Cuboid { ranges: [x, y, z] }
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<i64>; 3] {
+        &self.ranges
+    }
+
+    fn parse<'a>(input: &Input<'a>, s: &'a str) -> Result<Self, ParseError> {
+        let mut parts = s.split(',');
+        let mut ranges = [0..=0, 0..=0, 0..=0];
+        let mut previous = s;
+        for (range, axis) in ranges.iter_mut().zip(["x=", "y=", "z="]) {
+            let part = input.expect(parts.next(), previous, &format!("a {} range", axis))?;
+            let (from, to) = input.split_once(input.strip_prefix(part, axis)?, "..")?;
+            let from: i64 = input.parse(from)?;
+            let to: i64 = input.parse(to)?;
+
+            *range = std::cmp::min(from, to)..=std::cmp::max(from, to);
+            previous = part;
+        }
+        if let Some(part) = parts.next() {
+            return Err(input.error(part, "expected only x, y and z ranges"));
         }
+        Ok(Cuboid { ranges })
+    }
+
+    #[cfg(test)]
+    fn from_str(s: &str) -> Self {
+        Cuboid::parse(&Input::new(s), s).unwrap()
     }
 
-    fn count(&self) -> u64 {
+    /// Number of cubes in the cuboid.
+    pub fn count(&self) -> u64 {
         self.ranges
             .iter()
             .map(|r| r.start().abs_diff(r.end() + 1))
@@ -162,22 +182,21 @@ impl Cuboid {
                     ranges: ranges.try_into().unwrap(),
                 })
             }
-            added_sides.push([cut_l.clone(), cut_r.clone()]);
+            added_sides.push([*cut_l, *cut_r]);
         }
         cutted_cuboids
     }
 
+    /// Cuboids covering what's left of this one after taking out `rhs`, none when they don't overlap.
     #[inline]
-    fn substract(&self, rhs: &Self) -> Option<Vec<Cuboid>> {
+    pub fn substract(&self, rhs: &Self) -> Option<Vec<Cuboid>> {
         let overlaps = self.overlaps(rhs);
-        if overlaps.is_none() {
-            return None;
-        }
+        overlaps.as_ref()?;
         let overlaps = overlaps.unwrap();
         let first_overlap = &overlaps[0];
         let all_equal = overlaps.iter().skip(1).all(|o| o == first_overlap);
         match (all_equal, first_overlap) {
-            (true, Union::IsContained) => return Some(vec![]),
+            (true, Union::IsContained) => Some(vec![]),
             _ => {
                 let cuts = self.get_cuts(rhs, overlaps);
                 let remainder = self.outer_cut(cuts);
@@ -188,48 +207,72 @@ impl Cuboid {
 }
 
 type Command = (bool, Cuboid);
+
+/// Half the width of the initialization region of part 1, centered on the origin.
+pub const PARAMS: &[Param] = &[Param { name: "region", default: 50, min: 0 }];
+
+/// Reboot steps, each turning a cuboid on or off.
 pub struct ReactorReboot {
     commands: Vec<Command>,
+    region: i64,
 }
 
 impl crate::Advent for ReactorReboot {
-    fn new(data: &str) -> ReactorReboot {
-        let commands: Vec<Command> = data
+    fn new(data: &str) -> anyhow::Result<ReactorReboot> {
+        let input = Input::new(data);
+        let commands: Vec<Command> = input
             .lines()
             .map(|l| {
-                let (switch, ranges) = l.split_once(" ").unwrap();
+                let (switch, ranges) = input.split_once(l, " ")?;
                 let switch = match switch {
                     "on" => true,
                     "off" => false,
-                    _ => panic!("Invalid switch statement: {}", switch),
+                    _ => return Err(input.error(switch, "expected on or off")),
                 };
-                let cuboid = Cuboid::from_str(ranges);
+                let cuboid = Cuboid::parse(&input, ranges)?;
 
-                (switch, cuboid)
+                Ok((switch, cuboid))
             })
-            .collect();
-        ReactorReboot { commands }
+            .collect::<Result<_, _>>()?;
+        Ok(ReactorReboot { commands, region: PARAMS[0].default as i64 })
     }
 
-    fn part1(&mut self) -> usize {        
+    fn part1(&mut self) -> Answer {        
+        let region = self.region;
         let to_insert_cuboids: VecDeque<(bool, Cuboid)> =
             VecDeque::from_iter(self.commands.clone().into_iter().filter(|c| {
                 c.1.ranges
                     .iter()
-                    .all(|r| *r.start() >= -50 && *r.end() <= 50)
+                    .all(|r| *r.start() >= -region && *r.end() <= region)
             }));
-        reset_reactor(to_insert_cuboids)
+        reset_reactor(to_insert_cuboids).into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        reset_reactor(VecDeque::from(self.commands.clone())).into()
     }
 
-    fn part2(&mut self) -> usize {
-        reset_reactor(VecDeque::from(self.commands.clone()))
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn set_param(&mut self, _name: &str, value: usize) {
+        self.region = i64::try_from(value).unwrap_or(i64::MAX);
     }
 }
 
-fn reset_reactor(mut to_insert_cuboids: VecDeque<(bool, Cuboid)>) -> usize {
+/// Number of cubes left on after running the steps in order, `true` turning a cuboid on.
+///
+/// ```
+/// use advent_of_code::day_22::{reset_reactor, Cuboid};
+///
+/// let steps = vec![(true, Cuboid::new(10..=12, 10..=12, 10..=12)), (false, Cuboid::new(11..=13, 11..=13, 11..=13))];
+/// assert_eq!(reset_reactor(steps.into()), 19);
+/// ```
+pub fn reset_reactor(mut to_insert_cuboids: VecDeque<(bool, Cuboid)>) -> u64 {
     let mut current_cuboids: Vec<Cuboid> = vec![];
     'inserting: while let Some((switch, cuboid)) = to_insert_cuboids.pop_front() {
-        if switch == true {
+        if switch {
             for present_cuboid in &current_cuboids {
                 let overlap = cuboid.substract(present_cuboid);
                 if let Some(remainder) = overlap {
@@ -255,20 +298,22 @@ fn reset_reactor(mut to_insert_cuboids: VecDeque<(bool, Cuboid)>) -> usize {
             current_cuboids.extend(new_cuboids);
         }
     }
-    current_cuboids.iter().map(|c| c.count()).sum::<u64>() as usize
+    current_cuboids.iter().map(|c| c.count()).sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Advent;
     use itertools::Itertools;
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn coord_sub() {
-        assert_eq!(2 < 3, true);
-        assert_eq!(-1 < 2, true);
-        assert_eq!(-2 < -1, true);
-        assert_eq!(2 > -2, true);
+        assert!(2 < 3);
+        assert!(-1 < 2);
+        assert!(-2 < -1);
+        assert!(2 > -2);
     }
 
     #[test]
@@ -418,5 +463,13 @@ mod tests {
         let r4 = c1.substract(&c3).unwrap();
         assert_eq!(r4, [Cuboid { ranges: [34..=34, 24..=24, 16..=16]}]);
     }
+
+    #[test]
+    fn malformed_range_is_located() {
+        let err = ReactorReboot::new("on x=10..12,y=10..12,z=10..12\noff x=9..11,y=9..1a,z=9..11")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 2, column 18: unexpected '1a': invalid digit found in string");
+    }
 }
 
@@ -3,6 +3,8 @@ use std::io::{Error, ErrorKind};
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap};
 use hashbrown::HashMap;
+use crate::answer::Answer;
+use crate::parse::{self, Input, ParseError};
 
 type Space = Option<Amphipod>;
 
@@ -13,35 +15,47 @@ struct State<const R: usize> {
 }
 
 impl<const R: usize> State<R> {
-    fn from_str(data: &str) -> Self {
-        let mut it = data.lines().skip(1);
-        let hallway: [Space; 11] = it.next().unwrap().chars().filter_map(|c| {
-            match c {
-                '#' => None,
-                ' ' => None,
-                '.' => Some(None),
-                _ => Some(Some(Amphipod::try_from(c).unwrap()))
-            }
-        }).collect::<Vec<_>>().try_into().unwrap();
-        let rows: Vec<Vec<_>> = it.take(R).map(|l| {
-            l.chars().filter_map(|c| {
+    fn parse<'a>(input: &Input<'a>, lines: &[&'a str]) -> Result<Self, ParseError> {
+        let spaces = |line: &'a str, count: usize| -> Result<Vec<Space>, ParseError> {
+            let spaces = parse::chars(line).filter_map(|c| {
                 match c {
-                    '#' => None,
-                    ' ' => None,
-                    '.' => Some(None),
-                    _ => Some(Some(Amphipod::try_from(c).unwrap()))
+                    "#" => None,
+                    " " => None,
+                    "." => Some(Ok(None)),
+                    _ => Some(
+                        c.parse::<char>().ok()
+                            .and_then(|amphipod| Amphipod::try_from(amphipod).ok())
+                            .map(Some)
+                            .ok_or_else(|| input.error(c, "expected an amphipod A, B, C or D"))
+                    )
                 }
-            }).collect()
-        }).collect();
+            }).collect::<Result<Vec<_>, _>>()?;
+            if spaces.len() != count {
+                return Err(input.error(line, format!("expected {} spaces", count)));
+            }
+            Ok(spaces)
+        };
+        let line = |i: usize| {
+            input.expect(lines.get(i).copied(), lines.last().copied().unwrap_or_default(), "another row of the burrow")
+        };
+
+        let hallway: [Space; 11] = spaces(line(1)?, 11)?.try_into().unwrap();
+        let rows: Vec<Vec<_>> = (2..2 + R).map(|i| spaces(line(i)?, 4)).collect::<Result<_, _>>()?;
 
         let rooms: [[Space; R]; 4] = (0..4).map(|x| {
             let room: [Space; R] = rows.iter().map(|r| {
-                r[x].clone()
+                r[x]
             }).collect::<Vec<_>>().try_into().unwrap();
             room
         }).collect::<Vec<_>>().try_into().unwrap();
 
-        State { rooms, hallway }
+        Ok(State { rooms, hallway })
+    }
+
+    #[cfg(test)]
+    fn from_str(data: &str) -> Self {
+        let lines: Vec<&str> = data.lines().collect();
+        State::parse(&Input::new(data), &lines).unwrap()
     }
 
 
@@ -72,7 +86,7 @@ impl<const R: usize> State<R> {
         }
         let mut it = std::iter::from_fn(move || {
             let encoded_space = encoded % 5;
-            encoded = encoded / 5;
+            encoded /= 5;
             Some(decode_space(encoded_space))
         });
 
@@ -125,7 +139,7 @@ impl<const R: usize> State<R> {
     /// Checks whether a hiven hallway position is directly above one of the rooms
     #[inline]
     fn is_above_room(&self, x: usize) -> bool {
-        x >= 2 && x < self.hallway.len() - 2 && x % 2 == 0
+        x >= 2 && x < self.hallway.len() - 2 && x.is_multiple_of(2)
     }
     
     /// Check if an amphipod at start_x can freely move to target x.
@@ -222,7 +236,7 @@ impl<const R: usize> State<R> {
     /// Get all valid transitions from this state, together with their energy costs.
     fn transitions(&self) -> Vec<(State<R>, usize)> {
         let mut transitions = self.room_to_hallway_transitions();
-        transitions.extend(self.hallway_to_room_transitions().into_iter());
+        transitions.extend(self.hallway_to_room_transitions());
         transitions
     }
 
@@ -307,27 +321,27 @@ impl<const R: usize> State<R> {
 impl<const R: usize> fmt::Display for State<R> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "\n{:#<13}\n", "")?;
-        let hallway: String = self.hallway.clone().into_iter().map(|space| {
+        let hallway: String = self.hallway.into_iter().map(|space| {
             match space {
                 Some(a) => a.into(),
                 None => '.'
             }
         }).collect();
-        write!(f, "#{}#\n", hallway)?;
+        writeln!(f, "#{}#", hallway)?;
         for (i, row) in self.rows().iter().enumerate() {
-            let values: Vec<char> = row.clone().into_iter().map(|space| {
+            let values: Vec<char> = (*row).into_iter().map(|space| {
                 match space {
                     Some(a) => a.into(),
                     None => '.'
                 }
             }).collect();            
             if i == 0 {
-                write!(f, "###{}#{}#{}#{}###\n", values[0], values[1], values[2], values[3])?;
+                writeln!(f, "###{}#{}#{}#{}###", values[0], values[1], values[2], values[3])?;
             } else {
-                write!(f, "  #{}#{}#{}#{}#\n", values[0], values[1], values[2], values[3])?;
+                writeln!(f, "  #{}#{}#{}#{}#", values[0], values[1], values[2], values[3])?;
             }
         }
-        write!(f, "  {:#<9}\n", "")
+        writeln!(f, "  {:#<9}", "")
     }
 }
 
@@ -387,9 +401,9 @@ impl TryFrom<char> for Amphipod {
     }    
 }
 
-impl Into<char> for Amphipod {
-    fn into(self) -> char {
-        match self {
+impl From<Amphipod> for char {
+    fn from(val: Amphipod) -> Self {
+        match val {
             Amphipod::A => 'A',
             Amphipod::B => 'B',
             Amphipod::C => 'C',
@@ -461,26 +475,28 @@ pub struct Amphipods {
 }
 
 impl crate::Advent for Amphipods {
-    fn new(data: &str) -> Amphipods {
-        let original_state_01 = State::from_str(data);
-        let insert = "  #D#C#B#A#\n  #D#B#A#C#";
-        let result: Vec<&str> = data.lines().take(3).chain(
-            insert.lines()
+    fn new(data: &str) -> anyhow::Result<Amphipods> {
+        let input = Input::new(data);
+        let lines: Vec<&str> = input.lines().collect();
+        let original_state_01 = State::parse(&input, &lines)?;
+        let insert = ["  #D#C#B#A#", "  #D#B#A#C#"];
+        let result: Vec<&str> = lines[..3].iter().chain(
+            insert.iter()
         ).chain(
-            data.lines().skip(3)
-        ).collect();
-        let original_state_02 = State::from_str(result.join("\n").as_str());
+            lines[3..].iter()
+        ).copied().collect();
+        let original_state_02 = State::parse(&input, &result)?;
         // println!("State 01: {}", original_state_01);
         // println!("State 02: {}", original_state_02);
-        Amphipods { original_state_01, original_state_02 }
+        Ok(Amphipods { original_state_01, original_state_02 })
     }
 
-    fn part1(&mut self) -> usize {
-        find_min_score(self.original_state_01)
+    fn part1(&mut self) -> Answer {
+        find_min_score(self.original_state_01).into()
     }
     
-    fn part2(&mut self) -> usize {
-        find_min_score(self.original_state_02)
+    fn part2(&mut self) -> Answer {
+        find_min_score(self.original_state_02).into()
     }
 }
 
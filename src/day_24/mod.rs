@@ -1,3 +1,7 @@
+use std::str::FromStr;
+use crate::answer::Answer;
+use crate::parse::{Input, ParseError};
+
 #[derive(Debug, Clone, PartialEq)]
 enum Operation {
     Inp,
@@ -8,18 +12,23 @@ enum Operation {
     Eq
 }
 
-impl Operation {
-    fn from_str(s: &str) -> Self {
+impl FromStr for Operation {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "inp" => Self::Inp,
-            "add" => Self::Add,
-            "mul" => Self::Mul,
-            "div" => Self::Div,
-            "mod" => Self::Rem,
-            "eql" => Self::Eq,
-            _ => panic!("Invalid string {} for operations.", s)
+            "inp" => Ok(Self::Inp),
+            "add" => Ok(Self::Add),
+            "mul" => Ok(Self::Mul),
+            "div" => Ok(Self::Div),
+            "mod" => Ok(Self::Rem),
+            "eql" => Ok(Self::Eq),
+            _ => Err("expected inp, add, mul, div, mod or eql")
         }
     }
+}
+
+impl Operation {
     fn calculate(&self, lhs: &mut i64, rhs: i64) {
         match self {
             Self::Inp => *lhs = rhs,
@@ -56,13 +65,16 @@ type Args = (usize, Rhs);
  * variables, let's call them "a", "b" and "c". Written in pseudocode and using "w"
  * for the input value each block performs the following comutation:
  * 
+ * ```text
  *      x = (z % 26 + b) != w
  *      z /= a
  *      z *= 25 * x + 1
  *      z += (w + c) * x
+ * ```
  * 
  * This can be re-written using an if-block, which eliminates the x-register:
  * 
+ * ```text
  *      if z % 26 + b != w {
  *          z /= a
  *          z *= 26
@@ -70,6 +82,7 @@ type Args = (usize, Rhs);
  *      } else {
  *          z /= a
  *      }
+ * ```
  * 
  * We figure out from input, that "a" can only be one of two values: either 1 or 26.
  * This leads us to observe that all computations are manipulations of digits of the 
@@ -77,12 +90,15 @@ type Args = (usize, Rhs);
  * "shf" will be either 0 or 1. We can use binary operators to denote operations in 
  * base 26 as follows:
  * 
+ * ```text
  *      z * 26 = z << 1
  *      z / 26 = z >> 1
  *      z % 26 = z & 1
+ * ```
  * 
  * With this we can write the program as follows: 
  * 
+ * ```text
  *      if z & 1 + b != w {
  *          z = z >> shf
  *          z = z << 1 
@@ -90,13 +106,16 @@ type Args = (usize, Rhs);
  *      } else {
  *          z = z >> shf     
  *      }
+ * ```
  * 
  * We can also write the bitwise operations as follows: 
  * 
+ * ```text
  *      z & 1 = z.last_bit
  *      z >> 1 = z.pop()
  *      (z << 1) & q = z.push(q)
  *      ((z >> 1) << 1) & q = z.pop_push(q)
+ * ```
  * 
  * where pop/push refer to that bit stack of z in base 26 with the last bit 
  * on top. Therefore, z.pop() removes the last bit, z.push(q) appends 
@@ -104,6 +123,7 @@ type Args = (usize, Rhs);
  * 
  * Given that "shf" can only be 0 or 1 we get the following two cases:
  * 
+ * ```text
  *      if shf == 0 {
  *          if z.last_bit + b != w {
  *              z.push(w + c)
@@ -115,10 +135,12 @@ type Args = (usize, Rhs);
  *              z.pop()
  *          }
  *      }
+ * ```
  * Accordin to the puzzle input (our input) in all cases where shf == 0
  * it's true that b > 9. Given that 1 <= w <= 9 the check (if z.last_bit + b != w)
  * will therefore always be true. This gives:
  * 
+ * ```text
  *      if shf == 0 {
  *          z.push(w + c)
  *      } elif shf == 1 {
@@ -128,6 +150,7 @@ type Args = (usize, Rhs);
  *              z.pop_push(w + c)
  *          }
  *      }
+ * ```
  * 
  * We can summarize in words. View z as a stack of bits in base 26. Start with
  * an empty stack. Whenever shf == 0 (a == 1) push (w + c) on the stack. If,
@@ -145,13 +168,17 @@ type Args = (usize, Rhs);
  * If the first two instructions blocks have shf_0 == 0 and shf_1 == 0 then after the
  * first two inputs two bits will have been pushed to the stack:
  * 
+ * ```text
  *      z_stack = [w0 + c0, w1 + c1]
+ * ```
  * 
  * If then shf_2 == 1 we want to set w2 so that the last bit is popped. 
  * The last bit is popped if:
  * 
+ * ```text
  *      z.last_bit + b2 == w2
  *   => w1 + c1 + b2 == w2
+ * ```
  * 
  * So we set (w2 = w1 + c1 + b2). It can now occur that the condition 1 <= w2 <= 9 is
  * violated. In tis case we can add an arbitrary value to w2 to restore this condition.
@@ -172,7 +199,7 @@ type Args = (usize, Rhs);
  * to compute the correct input.
 **/
 fn correct_model_number(wish_input: [i64; 14], abcs: &[[i64; 3]; 14]) -> [i64; 14] {
-    let mut number = wish_input.clone();
+    let mut number = wish_input;
     let mut stack: Vec<(usize, i64)> = vec![];
     for (i, [a, b, c]) in abcs.iter().enumerate() {
         if *a == 1 {
@@ -181,11 +208,11 @@ fn correct_model_number(wish_input: [i64; 14], abcs: &[[i64; 3]; 14]) -> [i64; 1
             let (j, jc) = stack.pop().unwrap();
             number[i] = number[j] + jc + b;
             if number[i] > 9 {
-                number[j] = number[j] - (number[i] - 9);
+                number[j] -= number[i] - 9;
                 number[i] = 9;
             } 
             if number[i] < 1 {
-                number[j] = number[j] + (1 - number[i]);
+                number[j] += 1 - number[i];
                 number[i] = 1;
             }
         }
@@ -193,48 +220,102 @@ fn correct_model_number(wish_input: [i64; 14], abcs: &[[i64; 3]; 14]) -> [i64; 1
     number
 } 
 
+fn variable(input: &Input, s: &str) -> Result<usize, ParseError> {
+    match s {
+        "w" => Ok(0),
+        "x" => Ok(1),
+        "y" => Ok(2),
+        "z" => Ok(3),
+        _ => Err(input.error(s, "expected a variable w, x, y or z"))
+    }
+}
+
+/// Digits of a model number, each read by a block of the program starting with `inp`.
+const DIGITS: usize = 14;
+/// Positions of `div z a`, `add x b` and `add y c` in a block, see [`correct_model_number`].
+const ABC_OFFSETS: [usize; 3] = [4, 5, 15];
+
+/// Checks that the program is a MONAD, which the parts can find model numbers for: a block per
+/// digit with numbers at [`ABC_OFFSETS`], where every digit pushed on z is popped again.
+fn validate(input: &Input, data: &str, lines: &[&str], operations: &[(Operation, Args)]) -> Result<(), ParseError> {
+    let starts: Vec<usize> = (0..operations.len()).filter(|&i| operations[i].0 == Operation::Inp).collect();
+    if starts.first() != Some(&0) {
+        return match lines.first() {
+            Some(line) => Err(input.error(line, "expected the program to start with inp")),
+            None => Err(input.missing(data, "a MONAD program")),
+        };
+    }
+    if let Some(&extra) = starts.get(DIGITS) {
+        return Err(input.error(lines[extra], format!("expected {} digits, one per inp", DIGITS)));
+    }
+    if starts.len() < DIGITS {
+        return Err(input.missing(data, &format!("{} inp instructions, one per digit", DIGITS)));
+    }
+
+    let mut pushed = 0;
+    for (block, &start) in starts.iter().enumerate() {
+        let end = starts.get(block + 1).copied().unwrap_or(operations.len());
+        for offset in ABC_OFFSETS {
+            if start + offset >= end {
+                let reason = format!("expected a block of at least {} instructions", ABC_OFFSETS[2] + 1);
+                return Err(input.error(lines[start], reason));
+            }
+            let argument = lines[start + offset].rsplit(' ').next().unwrap_or_default();
+            match (offset, &operations[start + offset].1 .1) {
+                (4, Rhs::Value(1)) => pushed += 1,
+                (4, Rhs::Value(26)) if pushed == 0 => {
+                    return Err(input.error(argument, "pops a digit off z before one was pushed"))
+                }
+                (4, Rhs::Value(26)) => pushed -= 1,
+                (4, _) => return Err(input.error(argument, "expected z to be divided by 1 or 26")),
+                (_, Rhs::Value(_)) => {}
+                _ => return Err(input.error(argument, "expected a number")),
+            }
+        }
+    }
+    if pushed != 0 {
+        return Err(input.missing(data, "a block popping every digit pushed on z"));
+    }
+    Ok(())
+}
+
 pub struct ArithemticLogicUnit {
     operations: Vec<(Operation, Args)>,
 }
 
 impl crate::Advent for ArithemticLogicUnit {
-    fn new(data: &str) -> ArithemticLogicUnit {
-        let operations: Vec<(Operation, Args)> = data.lines().map(|l| {
-            let mut parts = l.split(" ");
-            let operation = Operation::from_str(parts.next().unwrap());
-            let lhs_char: &str = parts.next().unwrap();
-            let lhs: usize = match lhs_char.chars().next().unwrap() {
-                'w' => 0,
-                'x' => 1,
-                'y' => 2,
-                'z' => 3,
-                c => panic!("Invalid variable name {}", c)
-            };
+    fn new(data: &str) -> anyhow::Result<ArithemticLogicUnit> {
+        let input = Input::new(data);
+        let (lines, operations): (Vec<&str>, Vec<(Operation, Args)>) = input.lines().map(|l| {
+            let mut parts = l.split(' ');
+            let operation_str = input.expect(parts.next(), l, "an operation")?;
+            let operation: Operation = input.parse(operation_str)?;
+            let lhs_str = input.expect(parts.next(), operation_str, "a variable")?;
+            let lhs = variable(&input, lhs_str)?;
 
-            let rhs: Rhs = match parts.next() {
-                None => Rhs::None,
-                Some(s) => {
+            let rhs: Rhs = match (&operation, parts.next()) {
+                (Operation::Inp, None) => Rhs::None,
+                (Operation::Inp, Some(s)) => return Err(input.error(s, "inp takes a single variable")),
+                (_, None) => return Err(input.missing(lhs_str, "a variable or a number")),
+                (_, Some(s)) => {
                     let res: Result<i64, _> = s.parse();
                     if let Ok(val) = res {
                         Rhs::Value(val)
                     } else {
-                        let char_index = match s.chars().next().unwrap() {
-                            'w' => 0,
-                            'x' => 1,
-                            'y' => 2,
-                            'z' => 3,
-                            c => panic!("Invalid variable name {}", c)
-                        };
-                        Rhs::Variable(char_index)
+                        Rhs::Variable(variable(&input, s)?)
                     }
                 }
             };           
-            (operation, (lhs, rhs))            
-        }).collect();
-        ArithemticLogicUnit { operations }   
+            if let Some(s) = parts.next() {
+                return Err(input.error(s, "expected the end of the instruction"));
+            }
+            Ok((l, (operation, (lhs, rhs))))
+        }).collect::<Result<Vec<_>, _>>()?.into_iter().unzip();
+        validate(&input, data, &lines, &operations)?;
+        Ok(ArithemticLogicUnit { operations })
     }
 
-    fn part1(&mut self) -> usize {          
+    fn part1(&mut self) -> Answer {          
         let chunks = self.operation_chunks();  
         let abcs = extract_abcs(chunks);
         let number_arr = correct_model_number([9; 14], &abcs);
@@ -242,11 +323,10 @@ impl crate::Advent for ArithemticLogicUnit {
         if wxyz[3] != 0 {
             panic!("Part 1 was impossible to calculate.");
         }
-        let number = number_arr.iter().fold(0, |acc, digit| acc * 10 + digit) as usize;
-        number
+        model_number(number_arr).into()
     }
 
-    fn part2(&mut self) -> usize {
+    fn part2(&mut self) -> Answer {
         let chunks = self.operation_chunks();  
         let abcs = extract_abcs(chunks);
         let number_arr = correct_model_number([1; 14], &abcs);
@@ -254,20 +334,20 @@ impl crate::Advent for ArithemticLogicUnit {
         if wxyz[3] != 0 {
             panic!("Part 2 was impossible to calculate.");
         }
-        let number = number_arr.iter().fold(0, |acc, digit| acc * 10 + digit) as usize;
-        number
+        model_number(number_arr).into()
     }
 }
 
+/// Writes out the model number digit by digit
+fn model_number(digits: [i64; 14]) -> String {
+    digits.iter().map(|digit| digit.to_string()).collect()
+}
+
 /// Returns the three values for each chunk that are changing troought the chunks
 fn extract_abcs(chunks: Vec<Vec<(Operation, (usize, Rhs))>>) -> [[i64; 3]; 14] {
     let abcs: [[i64; 3]; 14] = chunks.iter().map(|chunk| {
         let abc: [i64; 3] = chunk.iter().enumerate().filter(|(i, _)| {
-            if [4, 5, 15].contains(i) {
-                true
-            } else {
-                false
-            }
+            [4, 5, 15].contains(i)
         }).map(|(_i, (_op, (_lhs, rhs)))| {
             match rhs {
                 Rhs::Value(x) => *x,
@@ -303,10 +383,11 @@ impl ArithemticLogicUnit {
 
     fn calculate(&mut self, number: [i64; 14]) -> [i64; 4] {
         let mut wxyz = [0; 4];
-        for ((op, (lhs, rhs)), digit) in self.operations.iter().zip(number) {
+        let mut digits = number.into_iter();
+        for (op, (lhs, rhs)) in &self.operations {
             match op {
                 Operation::Inp => {
-                    wxyz[0] = digit;
+                    wxyz[*lhs] = digits.next().unwrap_or_default();
                 },
                 _ => {
                     let value = match rhs {
@@ -333,12 +414,7 @@ impl ArithemticLogicUnit {
             let current_el = current.take().unwrap();
             let next_el = operation_iter.peek();
             let end_of_chunk = if let Some((op, _args)) = next_el {
-                match op {
-                    Operation::Inp  => {
-                        true
-                    }, 
-                    _ => false
-                }            
+                matches!(op, Operation::Inp)
             } else {
                 true
             };
@@ -353,4 +429,133 @@ impl ArithemticLogicUnit {
         }    
         chunks
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+    use std::collections::HashSet;
+    use proptest::prelude::*;
+
+    /// Block of a MONAD program reading one digit, `a` is 26 for the blocks which pop from z.
+    fn block(a: i64, b: i64, c: i64) -> String {
+        format!(
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\nmul y 0\nadd y 25\n\
+             mul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y",
+            a, b, c
+        )
+    }
+
+    /// MONAD programs of seven pushing and seven popping blocks in any balanced order, where
+    /// each popped digit has to be the pushed one plus an offset the digits can satisfy. z holds
+    /// at most five digits, one more than the puzzle input, as each one makes the search 9 times slower.
+    fn monad() -> impl Strategy<Value = String> {
+        let pairs = prop::collection::vec((1..=16i64, 10..=16i64, -8..=8i64, 1..=16i64), 7);
+        (prop::collection::vec(any::<bool>(), 14), pairs).prop_map(|(order, pairs)| {
+            let mut blocks = vec![];
+            let mut stack: Vec<usize> = vec![];
+            let mut pushed = 0;
+            for push in order {
+                if pushed < 7 && (stack.is_empty() || (push && stack.len() < 5)) {
+                    let (c, b, _, _) = pairs[pushed];
+                    stack.push(pushed);
+                    pushed += 1;
+                    blocks.push(block(1, b, c));
+                } else {
+                    let (c, _, offset, popped_c) = pairs[stack.pop().unwrap()];
+                    blocks.push(block(26, offset - c, popped_c));
+                }
+            }
+            blocks.join("\n")
+        })
+    }
+
+    fn run_block(block: &[(Operation, Args)], digit: i64, z: i64) -> i64 {
+        let mut wxyz = [0, 0, 0, z];
+        for (op, (lhs, rhs)) in block {
+            let value = match rhs {
+                Rhs::Value(v) => *v,
+                Rhs::Variable(var_index) => wxyz[*var_index],
+                Rhs::None => digit,
+            };
+            op.calculate(&mut wxyz[*lhs], value);
+        }
+        wxyz[3]
+    }
+
+    /// First model number the program accepts, trying the digits in the given order one block at
+    /// a time. Blocks reset w, x and y, so a dead end only depends on the block and z, which has to
+    /// stay below the product of the divisions still to come to get back to 0.
+    fn search(alu: &ArithemticLogicUnit, digits: &[i64]) -> Option<String> {
+        let blocks = alu.operation_chunks();
+        let mut limits = vec![1; blocks.len() + 1];
+        for (i, block) in blocks.iter().enumerate().rev() {
+            let divisor = block.iter().find_map(|(op, (lhs, rhs))| match (op, lhs, rhs) {
+                (Operation::Div, 3, Rhs::Value(v)) => Some(*v),
+                _ => None,
+            });
+            limits[i] = limits[i + 1] * divisor.unwrap_or(1);
+        }
+
+        fn go(blocks: &[Vec<(Operation, Args)>], limits: &[i64], digits: &[i64], z: i64, dead: &mut HashSet<(usize, i64)>, number: &mut String) -> bool {
+            let i = number.len();
+            if i == blocks.len() {
+                return z == 0;
+            }
+            if z >= limits[i] || dead.contains(&(i, z)) {
+                return false;
+            }
+            for &digit in digits {
+                number.push_str(&digit.to_string());
+                if go(blocks, limits, digits, run_block(&blocks[i], digit, z), dead, number) {
+                    return true;
+                }
+                number.pop();
+            }
+            dead.insert((i, z));
+            false
+        }
+
+        let mut number = String::new();
+        go(&blocks, &limits, digits, 0, &mut HashSet::new(), &mut number).then_some(number)
+    }
+
+    #[test]
+    fn rejects_programs_which_arent_monad() {
+        let err = ArithemticLogicUnit::new("").err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 1: expected a MONAD program");
+        let err = ArithemticLogicUnit::new("add z w\ninp w").err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 1: unexpected 'add z w': expected the program to start with inp");
+        let err = ArithemticLogicUnit::new(&block(1, 12, 4)).err().unwrap();
+        assert!(err.to_string().ends_with("expected 14 inp instructions, one per digit"), "{}", err);
+
+        let mut blocks: Vec<String> = (0..7).map(|_| block(1, 12, 4)).chain((0..7).map(|_| block(26, -3, 2))).collect();
+        blocks[0] = block(26, -3, 2);
+        let err = ArithemticLogicUnit::new(&blocks.join("\n")).err().unwrap();
+        assert_eq!(err.to_string(), "line 5, column 7: unexpected '26': pops a digit off z before one was pushed");
+        blocks[0] = block(1, 12, 4).replace("add y 4", "add y w");
+        let err = ArithemticLogicUnit::new(&blocks.join("\n")).err().unwrap();
+        assert_eq!(err.to_string(), "line 16, column 7: unexpected 'w': expected a number");
+    }
+
+    /// Cases of `matches_search`. Each one searches the program twice, which takes about 0.4s in
+    /// a debug build, so only 50 run by default; set `PROPTEST_CASES=5000` to run thousands,
+    /// preferably with `--release`.
+    fn search_cases() -> u32 {
+        std::env::var("PROPTEST_CASES").ok().and_then(|cases| cases.parse().ok()).unwrap_or(50)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(search_cases()))]
+
+        #[test]
+        fn matches_search(program in monad()) {
+            let mut alu = ArithemticLogicUnit::new(&program).unwrap();
+            let highest = search(&alu, &[9, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap();
+            let lowest = search(&alu, &[1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+            prop_assert_eq!(alu.part1(), highest.into());
+            prop_assert_eq!(alu.part2(), lowest.into());
+        }
+    }
+}
@@ -1,23 +1,25 @@
 use crate::euclidean::{Coordinate, Axis};
 use std::fmt;
+use crate::answer::Answer;
+use crate::parse::{self, Input};
+use crate::simulation::Simulation;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Cucumber {
     pos: Coordinate<u8>,
     dir: Axis
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Map {
     cucumbers: Vec<Vec<Option<Cucumber>>>,
     width: u8,
     height: u8
 }
 
-#[allow(dead_code)]
 impl fmt::Display for Map {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "\n")?;
+        writeln!(f)?;
         for row in &self.cucumbers {
             let row_str: String = row.iter().map(|space| {
                 match space {
@@ -31,10 +33,10 @@ impl fmt::Display for Map {
                 }
             }).collect();
 
-            write!(f, "{}\n", row_str)?;
+            writeln!(f, "{}", row_str)?;
         }
 
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
@@ -45,6 +47,85 @@ impl Map {
         let width = first.len() as u8;
         Map { cucumbers, width, height }
     }
+
+    /// Moves the east facing herd, then the south facing one, returning how many cucumbers moved.
+    fn step(&mut self) -> usize {
+        let mut num_moved = 0;
+        let east_swaps: Vec<Vec<(Coordinate<u8>, Coordinate<u8>)>> = self.cucumbers
+            .iter()
+            .map(|row| {
+                // Get east facing cucumbers
+                row.iter().filter_map(|space| {
+                    match space {
+                        Some(cucumber) => {
+                            match cucumber.dir {
+                                Axis::X => Some(cucumber),
+                                Axis::Y => None
+                            }
+                        }, 
+                        None => None
+                    }
+                // Get swaps if next position is clear
+                }).filter_map(|cucumber| {
+                    let next_pos = self.next_position(cucumber);
+                    let next_space = self.get_space(&next_pos);
+                    if next_space.is_none() {
+                        Some((cucumber.pos.clone(), next_pos))
+                    } else {
+                        None
+                    }
+                }).collect()                  
+            }).collect();        
+        // Move east facing cucumbers
+        self.cucumbers.iter_mut().zip(east_swaps).for_each(|(row, row_swaps)| {
+            for swap in row_swaps {
+                // row.swap(swap.0.x as usize, swap.1.x as usize);
+                let mut source = row[swap.0.x as usize].take();
+                if let Some(ref mut cucumber) = source {
+                    cucumber.pos = swap.1.clone();
+                }
+                let destination = std::mem::replace(&mut row[swap.1.x as usize], source);
+                row[swap.0.x as usize] = destination;                    
+                num_moved += 1;
+            }
+        });
+
+        let south_swaps: Vec<(Coordinate<u8>, Coordinate<u8>)> = self.cucumbers
+            .iter()
+            .flatten()
+            // Get south facing cucumbers
+            .filter_map(|space| {
+                match space {
+                    Some(cucumber) => {
+                        match cucumber.dir {
+                            Axis::X => None,
+                            Axis::Y => Some(cucumber)
+                        }
+                    },
+                    None => None
+                }
+            // Get swaps if next space is clear
+            }).filter_map(|cucumber| {
+                let next_pos = self.next_position(cucumber);
+                let next_space = self.get_space(&next_pos);
+                if next_space.is_none() {
+                    Some((cucumber.pos.clone(), next_pos))
+                } else {
+                    None
+                }
+            }).collect();
+
+        num_moved += south_swaps.len();
+        for swap in south_swaps {
+            let mut source = self.cucumbers[swap.0.y as usize][swap.0.x as usize].take();
+            if let Some(ref mut cucumber) = source {
+                cucumber.pos = swap.1.clone();
+            }
+            let destination = std::mem::replace(&mut self.cucumbers[swap.1.y as usize][swap.1.x as usize], source);
+            self.cucumbers[swap.0.y as usize][swap.0.x as usize] = destination;
+        }
+        num_moved
+    }
     fn next_position(&self, cucumber: &Cucumber) -> Coordinate<u8> {
         match cucumber.dir {
             Axis::X => {
@@ -74,118 +155,101 @@ impl Map {
     }
 }
 
+impl Simulation for Map {
+    fn step(&mut self) {
+        Map::step(self);
+    }
+
+    fn step_changed(&mut self) -> bool {
+        Map::step(self) != 0
+    }
+
+    fn fingerprint(&self) -> u64 {
+        // FNV-1a of the kind of every space, which is much cheaper than hashing the positions
+        // stored with the cucumbers again on every step
+        self.cucumbers.iter().flatten().fold(0xcbf29ce484222325, |hash, space| {
+            let kind = match space {
+                None => 0,
+                Some(Cucumber { dir: Axis::X, .. }) => 1,
+                Some(Cucumber { dir: Axis::Y, .. }) => 2,
+            };
+            (hash ^ kind).wrapping_mul(0x100000001b3)
+        })
+    }
+}
+
 pub struct SeaCucumber {
     map: Map
 }
 
 impl crate::Advent for SeaCucumber {
-    fn new(data: &str) -> SeaCucumber {
-        let cucumbers = data.lines().enumerate().map(|(y, l)| {
-            l.chars().enumerate().map(|(x, c)| {
+    fn new(data: &str) -> anyhow::Result<SeaCucumber> {
+        let input = Input::new(data);
+        let width = data.lines().next().unwrap_or_default().chars().count();
+        if width == 0 {
+            return Err(input.missing(data, "a map of sea cucumbers").into());
+        }
+        let cucumbers = input.lines().enumerate().map(|(y, l)| {
+            // Positions are stored as u8, which also has to fit the map size
+            if y >= u8::MAX as usize {
+                return Err(input.error(l, format!("expected at most {} rows", u8::MAX)));
+            }
+            if width > u8::MAX as usize {
+                return Err(input.error(l, format!("expected at most {} positions in a row", u8::MAX)));
+            }
+            if l.chars().count() != width {
+                return Err(input.error(l, format!("expected {} positions in every row", width)));
+            }
+            parse::chars(l).enumerate().map(|(x, c)| {
                 match c {
-                    '.' => None,
-                    '>' => Some(Cucumber { 
+                    "." => Ok(None),
+                    ">" => Ok(Some(Cucumber { 
                         pos: Coordinate { x: x as u8, y: y as u8},
                         dir: Axis::X,
-                    }),
-                    'v' => Some(Cucumber {
+                    })),
+                    "v" => Ok(Some(Cucumber {
                         pos: Coordinate { x: x as u8, y: y as u8 },
                         dir: Axis::Y
-                    }),
-                    _ => unreachable!()
+                    })),
+                    _ => Err(input.error(c, "expected '.', '>' or 'v'"))
                 }
             }).collect()          
-        }).collect();
+        }).collect::<Result<_, _>>()?;
         let map = Map::new(cucumbers);
-        SeaCucumber { map }
+        Ok(SeaCucumber { map })
     }
 
-    fn part1(&mut self) -> usize {
+    fn part1(&mut self) -> Answer {
         let mut map = self.map.clone();
-        let mut num_moved =  usize::MAX;
-        let mut iteration = 0;
-
-        while num_moved != 0 {            
-            num_moved = 0;
-            let east_swaps: Vec<Vec<(Coordinate<u8>, Coordinate<u8>)>> = map.cucumbers
-                .iter()
-                .map(|row| {
-                    // Get east facing cucumbers
-                    row.iter().filter_map(|space| {
-                        match space {
-                            Some(cucumber) => {
-                                match cucumber.dir {
-                                    Axis::X => Some(cucumber),
-                                    Axis::Y => None
-                                }
-                            }, 
-                            None => None
-                        }
-                    // Get swaps if next position is clear
-                    }).filter_map(|cucumber| {
-                        let next_pos = map.next_position(cucumber);
-                        let next_space = map.get_space(&next_pos);
-                        if next_space.is_none() {
-                            Some((cucumber.pos.clone(), next_pos))
-                        } else {
-                            None
-                        }
-                    }).collect()                  
-                }).collect();        
-            // Move east facing cucumbers
-            map.cucumbers.iter_mut().zip(east_swaps).for_each(|(row, row_swaps)| {
-                for swap in row_swaps {
-                    // row.swap(swap.0.x as usize, swap.1.x as usize);
-                    let mut source = std::mem::replace(&mut row[swap.0.x as usize], None);
-                    if let Some(ref mut cucumber) = source {
-                        cucumber.pos = swap.1.clone();
-                    }
-                    let destination = std::mem::replace(&mut row[swap.1.x as usize], source);
-                    row[swap.0.x as usize] = destination;                    
-                    num_moved += 1;
-                }
-            });
+        match map.run_until_stable() {
+            Ok(steps) => steps.into(),
+            Err(cycle) => panic!("The sea cucumbers never stop moving, {}", cycle),
+        }
+    }
 
-            let south_swaps: Vec<(Coordinate<u8>, Coordinate<u8>)> = map.cucumbers
-                .iter()
-                .flatten()
-                // Get south facing cucumbers
-                .filter_map(|space| {
-                    match space {
-                        Some(cucumber) => {
-                            match cucumber.dir {
-                                Axis::X => None,
-                                Axis::Y => Some(cucumber)
-                            }
-                        },
-                        None => None
-                    }
-                // Get swaps if next space is clear
-                }).filter_map(|cucumber| {
-                    let next_pos = map.next_position(cucumber);
-                    let next_space = map.get_space(&next_pos);
-                    if next_space.is_none() {
-                        Some((cucumber.pos.clone(), next_pos))
-                    } else {
-                        None
-                    }
-                }).collect();
+    fn part2(&mut self) -> Answer {
+        // The last day only has one puzzle
+        Answer::Empty
+    }
 
-            num_moved += south_swaps.len();
-            for swap in south_swaps {
-                let mut source = std::mem::replace(&mut map.cucumbers[swap.0.y as usize][swap.0.x as usize], None);
-                if let Some(ref mut cucumber) = source {
-                    cucumber.pos = swap.1.clone();
-                }
-                let destination = std::mem::replace(&mut map.cucumbers[swap.1.y as usize][swap.1.x as usize], source);
-                map.cucumbers[swap.0.y as usize][swap.0.x as usize] = destination;
-            }
-            iteration += 1;
-        }
-        iteration
+    fn visualization(&mut self) -> Option<&mut dyn crate::Visualize> {
+        Some(self)
     }
+}
 
-    fn part2(&mut self) -> usize {
-        2
+impl crate::Visualize for SeaCucumber {
+    fn visualize(&mut self, part: u8, frame: &mut dyn FnMut(String)) {
+        if part != 1 {
+            return;
+        }
+        let mut map = self.map.clone();
+        frame(format!("Step 0{}", map));
+        for step in 1.. {
+            let num_moved = map.step();
+            frame(format!("Step {}, {} moved{}", step, num_moved, map));
+            if num_moved == 0 {
+                break;
+            }
+        }
     }
 }
\ No newline at end of file
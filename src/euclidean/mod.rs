@@ -1,15 +1,18 @@
+//! Points on a two dimensional grid.
+
 use std::cmp::{Ordering, PartialEq};
 use std::ops::Index;
 use std::fmt;
 
-#[derive(Clone, Debug)]
+/// Axis to index a [`Coordinate`] with.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Axis {
     X,
     Y
 }
 
-#[allow(dead_code)]
-#[derive(Eq, Hash, Debug, Clone)]
+/// Position on a grid, ordered row by row.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub struct Coordinate<T> {
     pub x: T,
     pub y: T
@@ -21,12 +24,6 @@ impl<T: std::fmt::Display> fmt::Display for Coordinate<T> {
     }
 }
 
-impl<T: PartialEq> PartialEq for Coordinate<T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
 impl<T: Ord> Ord for Coordinate<T> {
     fn cmp(&self, other: &Self) -> Ordering {
         let y_cmp = self.y.cmp(&other.y);
@@ -58,15 +55,15 @@ impl Index<Axis> for Coordinate<u32> {
     }
 }
 
-#[derive(Eq, Hash, Debug)]
+/// Value at a position, ordered by position first.
+#[derive(PartialEq, Eq, Hash, Debug)]
 pub struct Point<T, U> {
     pub val: T,
     pub coordinate: Coordinate<U>
 }
 
-#[allow(dead_code)]
 impl<T, U> Point<T, U> {
-    fn new(val: T, coordinate: Coordinate<U>) -> Point<T, U> {
+    pub fn new(val: T, coordinate: Coordinate<U>) -> Point<T, U> {
         Point {val, coordinate}
     }
 }
@@ -77,12 +74,6 @@ impl<T: std::fmt::Display, U: std::fmt::Display> fmt::Display for Point<T, U> {
     }
 }
 
-impl<T: PartialEq, U: PartialEq> PartialEq for Point<T, U> {
-    fn eq(&self, other: &Self) -> bool {
-        self.val == other.val && self.coordinate == other.coordinate
-    }
-}
-
 impl<T: Ord, U: Ord> Ord for Point<T, U> {
     fn cmp(&self, other: &Self) -> Ordering {
         // self.val.cmp(&other.val)
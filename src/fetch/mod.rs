@@ -0,0 +1,200 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Context;
+use crate::inputs::day_dir;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u32 = 2021;
+
+/// Sent with every request, so the site can tell who is downloading the inputs.
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (input fetcher of an Advent of Code 2021 solutions repository, downloads each day once)"
+);
+
+/// Environment variable holding the session cookie, checked before the session file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Makes the requests of [`Fetcher`], swapped for a stub server in tests.
+pub trait Http {
+    /// Body of a successful GET, an error for any other status.
+    fn get(&self, url: &str, session: &str) -> anyhow::Result<String>;
+}
+
+/// [`Http`] over the network.
+pub struct Agent {
+    agent: ureq::Agent,
+}
+
+impl Agent {
+    pub fn new() -> Self {
+        Agent { agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build() }
+    }
+}
+
+impl Default for Agent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Http for Agent {
+    fn get(&self, url: &str, session: &str) -> anyhow::Result<String> {
+        let response = self.agent.get(url).set("Cookie", &format!("session={}", session)).call();
+        match response {
+            Ok(response) => response.into_string().with_context(|| format!("Could not read the response of {}", url)),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                anyhow::bail!("{} answered with {}: {}", url, status, body.trim())
+            }
+            Err(err) => Err(err).context("Could not reach the server"),
+        }
+    }
+}
+
+/// What [`Fetcher::fetch`] did for a day.
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already on disk, nothing was requested.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads puzzle inputs into `day_NN/input.txt` of the inputs directory.
+pub struct Fetcher<H: Http> {
+    http: H,
+    base_url: String,
+    session: String,
+    inputs_dir: PathBuf,
+}
+
+impl<H: Http> Fetcher<H> {
+    pub fn new(http: H, base_url: &str, session: &str, inputs_dir: &Path) -> Self {
+        Fetcher {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            inputs_dir: inputs_dir.to_path_buf(),
+        }
+    }
+
+    /// Downloads the day's input unless it is cached already.
+    pub fn fetch(&self, day: u32) -> anyhow::Result<Fetched> {
+        let dir = day_dir(&self.inputs_dir, day);
+        let path = dir.join("input.txt");
+        // An empty file is left behind by an interrupted download
+        if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        let input = self.http.get(&url, &self.session).with_context(|| format!("Could not download day {}", day))?;
+        fs::create_dir_all(&dir).with_context(|| format!("Could not create {}", dir.display()))?;
+        fs::write(&path, input).with_context(|| format!("Could not write {}", path.display()))?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+/// Default session file, `~/.config/advent_of_code/session`.
+pub fn default_session_file() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(".config/advent_of_code/session"))
+}
+
+/// Session cookie from `AOC_SESSION`, or else the first line of the session file.
+pub fn session(file: Option<&Path>) -> anyhow::Result<String> {
+    if let Some(session) = env::var(SESSION_VAR).ok().filter(|s| !s.trim().is_empty()) {
+        return Ok(session.trim().to_string());
+    }
+    let Some(file) = file else {
+        anyhow::bail!("No session token, set {} or pass --session-file", SESSION_VAR);
+    };
+    let content = fs::read_to_string(file).with_context(|| {
+        format!("No session token, set {} or store it in {}", SESSION_VAR, file.display())
+    })?;
+    match content.lines().next().map(str::trim) {
+        Some(session) if !session.is_empty() => Ok(session.to_string()),
+        _ => anyhow::bail!("Session file {} is empty", file.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers the given number of requests with `status` and `body`, sending back the request heads.
+    fn stub_server(requests: usize, status: &'static str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(&stream);
+                while reader.read_line(&mut head).unwrap() > 2 {}
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body)
+                    .unwrap();
+                sender.send(head).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_fetch_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn downloads_once() {
+        let (url, requests) = stub_server(1, "200 OK", "3,4,3,1,2\n");
+        let dir = temp_dir("once");
+        let fetcher = Fetcher::new(Agent::new(), &url, "secret", &dir);
+
+        let path = dir.join("day_06/input.txt");
+        assert_eq!(fetcher.fetch(6).unwrap(), Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3,4,3,1,2\n");
+        let head = requests.recv().unwrap().to_lowercase();
+        assert!(head.starts_with("get /2021/day/6/input http/1.1"));
+        assert!(head.contains("cookie: session=secret"));
+        assert!(head.contains(&format!("user-agent: {}", USER_AGENT.to_lowercase())));
+
+        // The server only answers once, a second request would fail
+        assert_eq!(fetcher.fetch(6).unwrap(), Fetched::Cached(path));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_refused_downloads() {
+        let (url, _requests) = stub_server(1, "400 Bad Request", "Please log in to get your puzzle input.");
+        let dir = temp_dir("refused");
+        let fetcher = Fetcher::new(Agent::new(), &url, "expired", &dir);
+
+        let err = fetcher.fetch(1).unwrap_err();
+        assert!(format!("{:#}", err).contains("answered with 400: Please log in to get your puzzle input."));
+        assert!(!dir.join("day_01/input.txt").exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn reads_session_file() {
+        let dir = temp_dir("session");
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("session");
+        fs::write(&file, "abc123\n").unwrap();
+        // Only checks the file when the variable isn't set for the whole test run
+        if env::var(SESSION_VAR).is_err() {
+            assert_eq!(session(Some(&file)).unwrap(), "abc123");
+            assert!(session(Some(&dir.join("missing"))).is_err());
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
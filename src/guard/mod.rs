@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::Duration;
+use advent_of_code::answer::Answer;
+use crate::memory::{self, Usage};
+use advent_of_code::Advent;
+use crate::{get_time, pool};
+
+/// How a single part of a day ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Ok,
+    Panic(String),
+    Timeout,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Outcome::Ok)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Ok => write!(f, "OK"),
+            Outcome::Panic(message) => write!(f, "PANIC: {}", message),
+            Outcome::Timeout => write!(f, "TIMEOUT"),
+        }
+    }
+}
+
+/// Answer of a part run by [`run`], with the time it took on its own thread.
+pub struct Part {
+    pub answer: Answer,
+    pub time: Duration,
+    pub cpu_time: Duration,
+    pub memory: Option<Usage>,
+    pub outcome: Outcome,
+}
+
+impl Part {
+    /// A part which didn't get to run.
+    pub fn skipped(outcome: Outcome) -> Self {
+        Part { answer: Answer::Empty, time: Duration::ZERO, cpu_time: Duration::ZERO, memory: None, outcome }
+    }
+}
+
+const THREAD_PREFIX: &str = "solve ";
+
+thread_local! {
+    // Message of the last panic on a part thread, filled in by the panic hook
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keeps panics of part threads off stderr, they are reported with the results instead.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !thread::current().name().is_some_and(|name| name.starts_with(THREAD_PREFIX)) {
+                return default(info);
+            }
+            let message = info
+                .payload()
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| info.payload().downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".to_string());
+            let message = match info.location() {
+                Some(location) => format!("{} at {}", message, location),
+                None => message,
+            };
+            PANIC_MESSAGE.with(|m| *m.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+/// Runs one part of a day on its own thread, catching panics and giving up after `timeout`.
+///
+/// The day is handed back unless the part panicked or is still running, in which
+/// case it has to be constructed again before solving the other part.
+pub fn run(
+    mut event: Box<dyn Advent>,
+    day: u32,
+    part: u8,
+    timeout: Option<Duration>,
+) -> (Option<Box<dyn Advent>>, Part) {
+    install_hook();
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name(format!("{}{}.{}", THREAD_PREFIX, day, part))
+        .spawn(move || {
+            let (((result, memory), time), cpu_time) = pool::cpu_time(|| {
+                get_time(|| {
+                    memory::measure(|| {
+                        panic::catch_unwind(AssertUnwindSafe(|| match part {
+                            1 => event.part1(),
+                            _ => event.part2(),
+                        }))
+                    })
+                })
+            });
+            let _ = match result {
+                Ok(answer) => sender.send((Some(event), Part { answer, time, cpu_time, memory, outcome: Outcome::Ok })),
+                Err(_) => {
+                    let message = PANIC_MESSAGE.with(|m| m.borrow_mut().take()).unwrap_or_default();
+                    let outcome = Outcome::Panic(message);
+                    sender.send((None, Part { answer: Answer::Empty, time, cpu_time, memory, outcome }))
+                }
+            };
+        });
+    if let Err(err) = spawned {
+        return (None, Part::skipped(Outcome::Panic(err.to_string())));
+    }
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).ok(),
+        None => receiver.recv().ok(),
+    };
+    match received {
+        Some(received) => received,
+        // The thread keeps running in the background until the runner exits
+        None => (None, Part { time: timeout.unwrap_or_default(), ..Part::skipped(Outcome::Timeout) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Stubborn;
+
+    impl Advent for Stubborn {
+        fn new(_: &str) -> anyhow::Result<Self> {
+            Ok(Stubborn)
+        }
+
+        fn part1(&mut self) -> Answer {
+            panic!("Puzzle is unsolvable!")
+        }
+
+        fn part2(&mut self) -> Answer {
+            thread::sleep(Duration::from_secs(5));
+            Answer::Empty
+        }
+    }
+
+    #[test]
+    fn reports_panics_and_timeouts() {
+        let (event, part) = run(Box::new(Stubborn), 0, 1, None);
+        assert!(event.is_none());
+        match part.outcome {
+            Outcome::Panic(message) => assert!(message.starts_with("Puzzle is unsolvable! at src/guard/mod.rs:")),
+            outcome => panic!("expected a panic, got {}", outcome),
+        }
+
+        let (event, part) = run(Box::new(Stubborn), 0, 2, Some(Duration::from_millis(10)));
+        assert!(event.is_none());
+        assert_eq!(part.outcome, Outcome::Timeout);
+    }
+}
@@ -0,0 +1,229 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+use anyhow::Context;
+use humantime::format_duration;
+use owo_colors::OwoColorize;
+use crate::report::{csv_field, Record};
+
+const HEADER: &str = "timestamp,commit,day,input,parse_ns,part1_ns,part2_ns,total_ns";
+
+/// Timings of one day in an earlier run.
+struct Entry {
+    day: u32,
+    input: String,
+    total: Duration,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Entry> {
+        let fields = split_fields(line);
+        if fields.len() != 8 {
+            return None;
+        }
+        Some(Entry {
+            day: fields[2].parse().ok()?,
+            input: fields[3].clone(),
+            total: Duration::from_nanos(fields[7].parse().ok()?),
+        })
+    }
+}
+
+/// Fields of a csv line, unquoting the ones [`csv_field`] quoted.
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// Timings of earlier runs, one line per day and run in a csv file.
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let mut entries = vec![];
+        if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            // A broken line only loses its own timings instead of every later comparison
+            for (i, line) in content.lines().enumerate().skip(1) {
+                match Entry::parse(line) {
+                    Some(entry) => entries.push(entry),
+                    None => eprintln!("Skipping invalid entry on line {} of {}", i + 1, path.display()),
+                }
+            }
+        }
+        Ok(History { path: path.to_path_buf(), entries })
+    }
+
+    /// Appends the records to the file, tagged with the current time and git commit.
+    pub fn append(&self, records: &[Record]) -> anyhow::Result<()> {
+        let mut content = String::new();
+        if !self.path.exists() {
+            content.push_str(HEADER);
+            content.push('\n');
+        }
+        let timestamp = humantime::format_rfc3339_seconds(SystemTime::now());
+        let commit = git_commit();
+        for r in records {
+            content.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                timestamp,
+                commit,
+                r.day,
+                csv_field(&r.input),
+                r.parse_time.as_nanos(),
+                r.time1.as_nanos(),
+                r.time2.as_nanos(),
+                r.total().as_nanos()
+            ));
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Could not open {}", self.path.display()))?;
+        file.write_all(content.as_bytes())
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+
+    fn baseline(&self, day: u32, input: &str, baseline: Baseline) -> Option<Duration> {
+        let mut totals = self.entries.iter().filter(|e| e.day == day && e.input == input).map(|e| e.total);
+        match baseline {
+            Baseline::Previous => totals.next_back(),
+            Baseline::Best => totals.min(),
+        }
+    }
+}
+
+/// Short hash of the checked out commit, marked when there are uncommitted changes.
+fn git_commit() -> String {
+    let git = |args: &[&str]| Command::new("git").args(args).current_dir(env!("CARGO_MANIFEST_DIR")).output().ok();
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(output) if output.status.success() => {
+            let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|o| !o.stdout.is_empty());
+            if dirty { format!("{}-dirty", commit) } else { commit }
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Earlier run to compare with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Baseline {
+    Previous,
+    Best,
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "previous" => Ok(Baseline::Previous),
+            "best" => Ok(Baseline::Best),
+            _ => Err(format!("Unknown baseline '{}', expected previous or best", s)),
+        }
+    }
+}
+
+/// Change of the time in percent, positive when it got slower.
+pub fn change(before: Duration, now: Duration) -> f64 {
+    (now.as_nanos() as f64 / before.as_nanos().max(1) as f64 - 1.0) * 100.0
+}
+
+/// Prints how each day's total time changed and returns how many got slower than `threshold` percent.
+pub fn print_comparison(history: &History, records: &[Record], baseline: Baseline, threshold: f64) -> usize {
+    let cell = |d: Duration| format!("{:>20}", format_duration(d).to_string());
+    let mut slower = 0;
+
+    println!("-----------------------------");
+    println!("{:>3} | {:<8} | {:>20} | {:>20} | {:>8} |", "Day", "Input", "Before", "Now", "Change");
+    for record in records {
+        let now = record.total();
+        let (before, change) = match history.baseline(record.day, &record.input, baseline) {
+            Some(before) => (cell(before), Some(change(before, now))),
+            None => (format!("{:>20}", "-"), None),
+        };
+        let flag = match change {
+            Some(change) if change > threshold => {
+                slower += 1;
+                format!("{}", "SLOWER".red())
+            }
+            _ => String::new(),
+        };
+        let change = change.map_or("-".to_string(), |c| format!("{:+.1}%", c));
+        println!(
+            "{:>3} | {:<8} | {} | {} | {:>8} | {}",
+            record.day, record.input, before, cell(now).fg_rgb::<255, 63, 128>(), change, flag
+        );
+    }
+    slower
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code::answer::Answer;
+    use crate::guard::Outcome;
+
+    fn entry(day: u32, millis: u64) -> Entry {
+        Entry { day, input: "input".to_string(), total: Duration::from_millis(millis) }
+    }
+
+    #[test]
+    fn baselines() {
+        let history = History { path: PathBuf::new(), entries: vec![entry(1, 30), entry(2, 5), entry(1, 10), entry(1, 20)] };
+        assert_eq!(history.baseline(1, "input", Baseline::Previous), Some(Duration::from_millis(20)));
+        assert_eq!(history.baseline(1, "input", Baseline::Best), Some(Duration::from_millis(10)));
+        assert_eq!(history.baseline(1, "example", Baseline::Best), None);
+        assert_eq!(change(Duration::from_millis(20), Duration::from_millis(25)), 25.0);
+    }
+
+    #[test]
+    fn keeps_inputs_with_commas() {
+        let path = std::env::temp_dir().join(format!("aoc_history_{}.csv", std::process::id()));
+        let history = History::load(&path).unwrap();
+        let record = Record {
+            day: 3,
+            name: "Binary Diagnostic",
+            input: "small, \"odd\"".to_string(),
+            parse_time: Duration::ZERO,
+            part1: Answer::Int(198),
+            time1: Duration::ZERO,
+            status1: Outcome::Ok,
+            part2: Answer::Int(230),
+            time2: Duration::ZERO,
+            status2: Outcome::Ok,
+            cpu_time: Duration::ZERO,
+            memory: [None; 3],
+        };
+        history.append(&[record]).unwrap();
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "2021-12-01,abc,1,input,oops\n").unwrap();
+
+        let history = History::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!((history.entries[0].day, history.entries[0].input.as_str()), (3, "small, \"odd\""));
+    }
+}
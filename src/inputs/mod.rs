@@ -0,0 +1,50 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use anyhow::Context;
+
+/// Folder holding the input, examples and answers of the day.
+pub fn day_dir(inputs_dir: &Path, day: u32) -> PathBuf {
+    inputs_dir.join(format!("day_{:02}", day))
+}
+
+/// The day's `example` and every file in its `examples` folder, e.g. `examples/small`.
+pub fn examples(day_dir: &Path) -> anyhow::Result<Vec<String>> {
+    let mut examples = vec![];
+    if day_dir.join("example.txt").exists() {
+        examples.push("example".to_string());
+    }
+    let dir = day_dir.join("examples");
+    if dir.exists() {
+        let mut named = vec![];
+        for entry in fs::read_dir(&dir).with_context(|| format!("Could not read {}", dir.display()))? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    named.push(format!("examples/{}", name));
+                }
+            }
+        }
+        named.sort();
+        examples.extend(named);
+    }
+    Ok(examples)
+}
+
+/// Reads `main_file` of the day, or the file given with `--input` where `-` is stdin.
+pub fn read_input(input: Option<&Path>, day_dir: &Path, main_file: &str) -> anyhow::Result<String> {
+    match input {
+        Some(path) if path == Path::new("-") => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content).context("Could not read input from stdin")?;
+            Ok(content)
+        }
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Could not read input file {}", path.display())),
+        None => {
+            let path = day_dir.join(format!("{}.txt", main_file));
+            fs::read_to_string(&path)
+                .with_context(|| format!("Could not read {} file {}", main_file, path.display()))
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Solutions of Advent of Code 2021.
+//!
+//! Every day has a solver type in its own module, e.g. [`day_06::Lanternfish`], which
+//! parses the puzzle input and answers both parts through the [`Advent`] trait:
+//!
+//! ```
+//! use advent_of_code::Advent;
+//! use advent_of_code::day_06::Lanternfish;
+//!
+//! let mut fish = Lanternfish::new("3,4,3,1,2").unwrap();
+//! assert_eq!(fish.part1().to_string(), "5934");
+//! ```
+//!
+//! The helpers shared by the solutions live in [`euclidean`], [`data_structures`],
+//! [`simulation`] and [`ocr`]. The `advent_of_code` binary runs them from the command line.
+
+// #![feature(drain_filter)]
+// #![feature(slice_group_by)]
+
+pub mod answer;
+pub mod data_structures;
+pub mod euclidean;
+pub mod ocr;
+pub mod params;
+pub(crate) mod parse;
+pub mod simulation;
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+
+use answer::Answer;
+
+/// A day of the calendar, parsed from its puzzle input.
+pub trait Advent: Send {
+    fn new(data: &str) -> anyhow::Result<Self>
+    where
+        Self: Sized;
+    fn part1(&mut self) -> Answer;
+    fn part2(&mut self) -> Answer;
+
+    /// Days which can show their simulation return themselves here.
+    fn visualization(&mut self) -> Option<&mut dyn Visualize> {
+        None
+    }
+
+    /// Constants of the puzzle which `--param` can change, with their defaults.
+    fn params(&self) -> &'static [params::Param] {
+        &[]
+    }
+
+    /// Changes one of the [`params`](Advent::params), only called with a declared name.
+    fn set_param(&mut self, _name: &str, _value: usize) {}
+}
+
+/// Days which can show their simulation step by step.
+pub trait Visualize {
+    /// Solves the part again, passing a rendering of each step to `frame`.
+    fn visualize(&mut self, part: u8, frame: &mut dyn FnMut(String));
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+
+/// Heap usage of the current thread while running some code.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    /// Most bytes held at once, on top of what the thread held before.
+    pub peak: usize,
+    pub allocations: usize,
+    /// Bytes allocated in total, including the ones freed again.
+    pub total: usize,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "peak {}, {} allocations, {} total", bytes(self.peak), self.allocations, bytes(self.total))
+    }
+}
+
+fn bytes(n: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if n < 1024 {
+        return format!("{} B", n);
+    }
+    let mut value = n as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Calls the function, counting the allocations it makes on the current thread.
+///
+/// Only measures with the `memory` feature, which swaps in a counting global allocator.
+#[cfg(feature = "memory")]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    counting::measure(f)
+}
+
+#[cfg(not(feature = "memory"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    (f(), None)
+}
+
+#[cfg(feature = "memory")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use super::Usage;
+
+    struct Counting;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    // Memory freed by another thread than the one it was allocated on makes `CURRENT` go negative
+    thread_local! {
+        static CURRENT: Cell<isize> = const { Cell::new(0) };
+        static PEAK: Cell<isize> = const { Cell::new(0) };
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+        static TOTAL: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn allocated(size: usize) {
+        // Thread locals can already be gone while a thread shuts down
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + size as isize);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        let _ = TOTAL.try_with(|total| total.set(total.get() + size));
+    }
+
+    fn freed(size: usize) {
+        let _ = CURRENT.try_with(|current| current.set(current.get() - size as isize));
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: forwarded unchanged to the system allocator
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: forwarded unchanged to the system allocator
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            // SAFETY: forwarded unchanged to the system allocator
+            unsafe { System.dealloc(ptr, layout) };
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            // SAFETY: forwarded unchanged to the system allocator
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+        let start = CURRENT.with(Cell::get);
+        let allocations = ALLOCATIONS.with(Cell::get);
+        let total = TOTAL.with(Cell::get);
+        // Track the peak from here on, restoring the outer one afterwards for nested calls
+        let outer_peak = PEAK.with(|peak| peak.replace(start));
+
+        let result = f();
+
+        let peak = PEAK.with(|peak| peak.replace(outer_peak.max(peak.get())));
+        let usage = Usage {
+            peak: (peak - start).max(0) as usize,
+            allocations: ALLOCATIONS.with(Cell::get) - allocations,
+            total: TOTAL.with(Cell::get) - total,
+        };
+        (result, Some(usage))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn counts_allocations() {
+            let (_, usage) = measure(|| {
+                let a = vec![0u8; 1000];
+                drop(a);
+                let b = vec![0u8; 600];
+                b.len()
+            });
+            let usage = usage.unwrap();
+            assert_eq!(usage.allocations, 2);
+            assert_eq!(usage.total, 1600);
+            assert_eq!(usage.peak, 1000);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(bytes(512), "512 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,185 @@
+use std::error::Error;
+use std::fmt;
+use crate::euclidean::Coordinate;
+
+const GLYPH_WIDTH: u32 = 4;
+const GLYPH_HEIGHT: u32 = 6;
+// Glyphs are separated by a single empty column
+const GLYPH_SPACING: u32 = GLYPH_WIDTH + 1;
+
+/// Capital letters of the 4x6 font AoC uses for its "read the screen" puzzles.
+const ALPHABET: [(&str, char); 17] = [
+    (".##.\n#..#\n#..#\n####\n#..#\n#..#", 'A'),
+    ("###.\n#..#\n###.\n#..#\n#..#\n###.", 'B'),
+    (".##.\n#..#\n#...\n#...\n#..#\n.##.", 'C'),
+    ("####\n#...\n###.\n#...\n#...\n####", 'E'),
+    ("####\n#...\n###.\n#...\n#...\n#...", 'F'),
+    (".##.\n#..#\n#...\n#.##\n#..#\n.###", 'G'),
+    ("#..#\n#..#\n####\n#..#\n#..#\n#..#", 'H'),
+    (".###\n..#.\n..#.\n..#.\n..#.\n.###", 'I'),
+    ("..##\n...#\n...#\n...#\n#..#\n.##.", 'J'),
+    ("#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#", 'K'),
+    ("#...\n#...\n#...\n#...\n#...\n####", 'L'),
+    (".##.\n#..#\n#..#\n#..#\n#..#\n.##.", 'O'),
+    ("###.\n#..#\n#..#\n###.\n#...\n#...", 'P'),
+    ("###.\n#..#\n#..#\n###.\n#.#.\n#..#", 'R'),
+    (".###\n#...\n#...\n.##.\n...#\n###.", 'S'),
+    ("#..#\n#..#\n#..#\n#..#\n#..#\n.##.", 'U'),
+    ("####\n...#\n..#.\n.#..\n#...\n####", 'Z'),
+];
+
+/// Glyphs that didn't match any letter, keyed by the column they start at.
+#[derive(Debug)]
+pub struct OcrError {
+    unrecognised: Vec<(u32, String)>,
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let columns: Vec<String> = self.unrecognised.iter().map(|(column, _)| column.to_string()).collect();
+        write!(f, "Unrecognised glyphs at columns {}", columns.join(", "))?;
+        for (column, glyph) in &self.unrecognised {
+            write!(f, "\n\nColumn {}:\n{}", column, glyph)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for OcrError {}
+
+/// Reads the capital letters drawn by the given dots.
+///
+/// Letters are expected to start in the top left corner, each being 4 columns
+/// wide and separated by one empty column.
+pub fn recognize(dots: &[Coordinate<u32>]) -> Result<String, OcrError> {
+    let width = match dots.iter().map(|c| c.x).max() {
+        Some(max_x) => max_x + 1,
+        None => return Ok(String::new()),
+    };
+    // Dots below the font height still get drawn, so such glyphs never match
+    let height = dots.iter().map(|c| c.y + 1).max().unwrap_or(0).max(GLYPH_HEIGHT);
+    let num_glyphs = width.div_ceil(GLYPH_SPACING);
+
+    let mut text = String::new();
+    let mut unrecognised: Vec<(u32, String)> = vec![];
+    for i in 0..num_glyphs {
+        let column = i * GLYPH_SPACING;
+        let glyph = render_glyph(dots, column, height);
+        // A dot in the gap after the glyph means letters aren't aligned to the font
+        let spills_over = dots.iter().any(|c| c.x == column + GLYPH_WIDTH);
+        match ALPHABET.iter().find(|(pattern, _)| *pattern == glyph) {
+            Some((_, letter)) if !spills_over => text.push(*letter),
+            _ => unrecognised.push((column, glyph)),
+        }
+    }
+
+    if unrecognised.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError { unrecognised })
+    }
+}
+
+/// Reads the letters of a grid drawn row by row with '#' for the dots, like day 13 answers
+/// with when its sheet doesn't fold into letters.
+pub fn recognize_grid(rows: &[String]) -> Result<String, OcrError> {
+    let dots: Vec<Coordinate<u32>> = rows.iter().enumerate().flat_map(|(y, row)| {
+        row.chars().enumerate()
+            .filter(|(_, c)| *c == '#')
+            .map(move |(x, _)| Coordinate { x: x as u32, y: y as u32 })
+    }).collect();
+    recognize(&dots)
+}
+
+fn render_glyph(dots: &[Coordinate<u32>], column: u32, height: u32) -> String {
+    let mut rows = vec![vec!['.'; GLYPH_WIDTH as usize]; height as usize];
+    dots.iter()
+        .filter(|c| (column..column + GLYPH_WIDTH).contains(&c.x))
+        .for_each(|c| rows[c.y as usize][(c.x - column) as usize] = '#');
+    rows.into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(screen: &str) -> Vec<String> {
+        screen.lines().map(String::from).collect()
+    }
+
+    fn dots(screen: &str) -> Vec<Coordinate<u32>> {
+        grid(screen).iter().enumerate().flat_map(|(y, line)| {
+            line.chars().enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| Coordinate { x: x as u32, y: y as u32 })
+        }).collect()
+    }
+
+    #[test]
+    fn reads_letters() {
+        let screen = dots(
+            "####..##..####.#..#.#....#..#.####.####.
+#....#..#.#....#..#.#....#..#....#.#....
+###..#....###..####.#....####...#..###..
+#....#....#....#..#.#....#..#..#...#....
+#....#..#.#....#..#.#....#..#.#....#....
+####..##..#....#..#.####.#..#.####.#...."
+        );
+        assert_eq!(recognize(&screen).unwrap(), "ECFHLHZF");
+    }
+
+    #[test]
+    fn reports_unrecognised_columns() {
+        let screen = dots(
+            "#..#.#..#.####
+#..#.##.#.#...
+####.#.##.###.
+#..#.#..#.#...
+#..#.#..#.#...
+#..#.#..#.####"
+        );
+        let err = recognize(&screen).unwrap_err();
+        assert_eq!(err.unrecognised.len(), 1);
+        assert_eq!(err.unrecognised[0].0, 5);
+    }
+
+    #[test]
+    fn reads_grids() {
+        let sheet = grid("#..#.####\n#..#.#...\n####.###.\n#..#.#...\n#..#.#...\n#..#.####");
+        assert_eq!(recognize_grid(&sheet).unwrap(), "HE");
+        let err = recognize_grid(&grid("#####\n#...#\n#...#\n#...#\n#####")).unwrap_err();
+        assert!(err.to_string().starts_with("Unrecognised glyphs at columns 0"));
+    }
+
+    #[test]
+    fn glyph_taller_than_font_is_unrecognised() {
+        let mut screen = dots(
+            "#...
+#...
+#...
+#...
+#...
+####"
+        );
+        screen.push(Coordinate { x: 0, y: 6 });
+        let err = recognize(&screen).unwrap_err();
+        assert_eq!(err.unrecognised[0].0, 0);
+    }
+
+    #[test]
+    fn dot_between_glyphs_is_unrecognised() {
+        let screen = dots(
+            "####.
+#....
+###..
+#...#
+#....
+####."
+        );
+        let err = recognize(&screen).unwrap_err();
+        assert_eq!(err.unrecognised[0].0, 0);
+    }
+}
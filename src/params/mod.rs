@@ -0,0 +1,97 @@
+use crate::Advent;
+
+/// A constant of a puzzle which `--param` can change, e.g. the days `Lanternfish` simulates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: usize,
+    /// Smallest value the day can be solved with.
+    pub min: usize,
+}
+
+/// Values given with `--param NAME=VALUE`, set on every selected day.
+#[derive(Clone, Debug, Default)]
+pub struct Params {
+    values: Vec<(String, usize)>,
+}
+
+impl Params {
+    pub fn new(values: Vec<(String, usize)>) -> Self {
+        Params { values }
+    }
+
+    /// Fails for a name the day doesn't declare or a value below its minimum.
+    pub fn validate(&self, declared: &[Param]) -> anyhow::Result<()> {
+        for (name, value) in &self.values {
+            match declared.iter().find(|param| param.name == name) {
+                Some(param) if *value < param.min => {
+                    anyhow::bail!("Parameter '{}' needs a value of at least {}, got {}", name, param.min, value)
+                }
+                Some(_) => {}
+                None if declared.is_empty() => anyhow::bail!("No parameter '{}', the day has none", name),
+                None => {
+                    let names: Vec<&str> = declared.iter().map(|param| param.name).collect();
+                    anyhow::bail!("No parameter '{}', available are {}", name, names.join(", "));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the values on the day, failing like [`Params::validate`].
+    pub fn apply(&self, event: &mut dyn Advent) -> anyhow::Result<()> {
+        self.validate(event.params())?;
+        for (name, value) in &self.values {
+            event.set_param(name, *value);
+        }
+        Ok(())
+    }
+}
+
+/// Parses `NAME=VALUE` of `--param`.
+pub fn parse_assignment(s: &str) -> Result<(String, usize), String> {
+    let (name, value) = s.split_once('=').ok_or_else(|| format!("expected NAME=VALUE, got '{}'", s))?;
+    let value = value.trim().parse().map_err(|err| format!("invalid value of {}: {}", name.trim(), err))?;
+    Ok((name.trim().to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::day_06::Lanternfish;
+
+    #[test]
+    fn parses_assignments() {
+        assert_eq!(parse_assignment("steps=500"), Ok(("steps".to_string(), 500)));
+        assert!(parse_assignment("steps").is_err());
+        assert!(parse_assignment("steps=-1").is_err());
+    }
+
+    #[test]
+    fn sets_declared_params() {
+        let mut fish = Lanternfish::new("3,4,3,1,2").unwrap();
+        Params::new(vec![("days1".to_string(), 18)]).apply(&mut fish).unwrap();
+        assert_eq!(fish.part1(), Answer::Int(26));
+        assert_eq!(fish.part2(), Answer::Int(26984457539));
+
+        let err = Params::new(vec![("steps".to_string(), 5)]).apply(&mut fish).unwrap_err();
+        assert_eq!(err.to_string(), "No parameter 'steps', available are days1, days2");
+    }
+
+    #[test]
+    #[should_panic(expected = "number of fish overflows")]
+    fn reports_overflow_instead_of_wrapping() {
+        let mut fish = Lanternfish::new("3,4,3,1,2").unwrap();
+        Params::new(vec![("days2".to_string(), 1000)]).apply(&mut fish).unwrap();
+        fish.part2();
+    }
+
+    #[test]
+    fn rejects_values_below_the_minimum() {
+        let declared = [Param { name: "tiles", default: 5, min: 1 }];
+        assert!(Params::new(vec![("tiles".to_string(), 1)]).validate(&declared).is_ok());
+        let err = Params::new(vec![("tiles".to_string(), 0)]).validate(&declared).unwrap_err();
+        assert_eq!(err.to_string(), "Parameter 'tiles' needs a value of at least 1, got 0");
+    }
+}
@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Line, column and token of a malformed piece of puzzle input.
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    token: String,
+    reason: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        if self.token.is_empty() {
+            write!(f, "{}", self.reason)
+        } else {
+            write!(f, "unexpected '{}': {}", self.token, self.reason)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Puzzle input which can point errors back at the token that caused them.
+///
+/// Tokens have to be slices of the input itself, their position is recovered
+/// from where they start in memory.
+#[derive(Clone, Copy)]
+pub struct Input<'a> {
+    data: &'a str,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(data: &'a str) -> Self {
+        Input { data }
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.data.lines()
+    }
+
+    /// One based line and column where the token starts.
+    fn locate(&self, token: &str) -> (usize, usize) {
+        let offset = (token.as_ptr() as usize)
+            .saturating_sub(self.data.as_ptr() as usize)
+            .min(self.data.len());
+        let before = self.data.get(..offset).unwrap_or(self.data);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+    }
+
+    pub fn error(&self, token: &str, reason: impl fmt::Display) -> ParseError {
+        let (line, column) = self.locate(token);
+        ParseError { line, column, token: token.to_string(), reason: reason.to_string() }
+    }
+
+    /// Error for something missing right after the given token.
+    pub fn missing(&self, after: &str, what: &str) -> ParseError {
+        self.error(&after[after.len()..], format!("expected {}", what))
+    }
+
+    pub fn parse<T>(&self, token: &'a str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        token.parse().map_err(|err| self.error(token, err))
+    }
+
+    /// Returns the token, or an error pointing after `after` when there is none.
+    pub fn expect(&self, token: Option<&'a str>, after: &'a str, what: &str) -> Result<&'a str, ParseError> {
+        token.ok_or_else(|| self.missing(after, what))
+    }
+
+    /// Splits the token in two, like `str::split_once`, erroring if the delimiter is missing.
+    pub fn split_once(&self, token: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        token
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(token, format!("expected '{}'", delimiter)))
+    }
+
+    /// Strips the prefix from the token, erroring if it isn't there.
+    pub fn strip_prefix(&self, token: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        token
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(token, format!("expected '{}'", prefix)))
+    }
+}
+
+/// Every character of the string as its own token.
+pub fn chars(s: &str) -> impl Iterator<Item = &str> {
+    s.char_indices().map(move |(i, c)| &s[i..i + c.len_utf8()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_token() {
+        let data = "forward 5\ndown 8\nup x3";
+        let input = Input::new(data);
+        let token = data.lines().nth(2).unwrap().split(' ').nth(1).unwrap();
+        let err = input.parse::<u32>(token).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 4));
+        assert_eq!(err.to_string(), "line 3, column 4: unexpected 'x3': invalid digit found in string");
+    }
+
+    #[test]
+    fn missing_token_points_after_previous() {
+        let data = "forward 5\ndown";
+        let input = Input::new(data);
+        let line = data.lines().nth(1).unwrap();
+        let err = input.missing(line, "a number");
+        assert_eq!(err.to_string(), "line 2, column 5: expected a number");
+    }
+}
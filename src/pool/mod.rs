@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Runs `task` for every day on `jobs` worker threads.
+///
+/// Results are handed to `handle` on the calling thread in the order of `days`,
+/// as soon as all the days before them are done, together with the CPU time
+/// the task took. Workers stop picking up new days once `handle` returns an error.
+pub fn run<D, T, Task, Handle>(days: &[D], jobs: usize, task: Task, mut handle: Handle) -> anyhow::Result<()>
+where
+    D: Copy + Sync,
+    T: Send,
+    Task: Fn(D) -> T + Sync,
+    Handle: FnMut(D, T, Duration) -> anyhow::Result<()>,
+{
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            let (next, stop, task) = (&next, &stop, &task);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&day) = days.get(i) else { break };
+                    if sender.send((i, cpu_time(|| task(day)))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Results which arrived before some day that comes earlier
+        let mut pending: BTreeMap<usize, (T, Duration)> = BTreeMap::new();
+        let mut next_to_handle = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some((result, time)) = pending.remove(&next_to_handle) {
+                if let Err(err) = handle(days[next_to_handle], result, time) {
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(err);
+                }
+                next_to_handle += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Calls the function, measuring the CPU time the current thread spent on it.
+#[cfg(unix)]
+pub fn cpu_time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    fn now() -> Duration {
+        let mut time = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        // SAFETY: clock_gettime only writes into the timespec it is given
+        unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+        Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    }
+    let start = now();
+    let result = f();
+    (result, now().saturating_sub(start))
+}
+
+/// Falls back to wall-clock time where per thread CPU time isn't available.
+#[cfg(not(unix))]
+pub fn cpu_time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    crate::get_time(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_results_in_day_order() {
+        let days: Vec<u32> = (1..=8).collect();
+        let mut handled = vec![];
+        run(
+            &days,
+            4,
+            |day| {
+                // Earlier days finish last
+                thread::sleep(Duration::from_millis(5 * (8 - day as u64)));
+                day * 10
+            },
+            |day, result, _| {
+                handled.push((day, result));
+                Ok(())
+            },
+        ).unwrap();
+        assert_eq!(handled, days.iter().map(|&d| (d, d * 10)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stops_after_error() {
+        let days: Vec<u32> = (1..=5).collect();
+        let mut handled = vec![];
+        let result = run(&days, 1, |day| day, |day, _, _| {
+            handled.push(day);
+            if day == 2 { Err(anyhow::anyhow!("failed")) } else { Ok(()) }
+        });
+        assert!(result.is_err());
+        assert_eq!(handled, vec![1, 2]);
+    }
+}
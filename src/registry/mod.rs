@@ -0,0 +1,241 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use advent_of_code::params::Param;
+use crate::{construct, Constructor};
+use advent_of_code::{
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12, day_13,
+    day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22, day_23, day_24, day_25,
+};
+
+/// A solved puzzle which the runner knows about.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub day: u32,
+    pub title: &'static str,
+    pub constructor: Constructor,
+    /// What the day returns from [`Advent::params`](advent_of_code::Advent::params), known without an input.
+    pub params: &'static [Param],
+}
+
+/// Every implemented day, ordered by day number.
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle { day: 1, title: "Sonar Sweep", constructor: construct::<day_01::SonarSweep>, params: &[] },
+    Puzzle { day: 2, title: "Dive!", constructor: construct::<day_02::Dive>, params: &[] },
+    Puzzle { day: 3, title: "Binary Diagnostic", constructor: construct::<day_03::BinaryDiagnostic>, params: &[] },
+    Puzzle { day: 4, title: "Giant Squid", constructor: construct::<day_04::GiantSquid>, params: &[] },
+    Puzzle { day: 5, title: "Hydrothermal Venture", constructor: construct::<day_05::HydrothermalVenture>, params: &[] },
+    Puzzle { day: 6, title: "Lanternfish", constructor: construct::<day_06::Lanternfish>, params: day_06::PARAMS },
+    Puzzle { day: 7, title: "The Treachery of Whales", constructor: construct::<day_07::TheThreacheryOfWhales>, params: &[] },
+    Puzzle { day: 8, title: "Seven Segment Search", constructor: construct::<day_08::SevenSegmentSearch>, params: &[] },
+    Puzzle { day: 9, title: "Smoke Basin", constructor: construct::<day_09::SmokeBasin>, params: &[] },
+    Puzzle { day: 10, title: "Syntax Scoring", constructor: construct::<day_10::SyntaxScoring>, params: &[] },
+    Puzzle { day: 11, title: "Dumbo Octopus", constructor: construct::<day_11::DumboOctopus>, params: day_11::PARAMS },
+    Puzzle { day: 12, title: "Passage Pathing", constructor: construct::<day_12::PassagePassing>, params: &[] },
+    Puzzle { day: 13, title: "Transparent Origami", constructor: construct::<day_13::TransparentOrigami>, params: &[] },
+    Puzzle { day: 14, title: "Extended Polymerization", constructor: construct::<day_14::ExtendedPolymerization>, params: day_14::PARAMS },
+    Puzzle { day: 15, title: "Chiton", constructor: construct::<day_15::Chiton>, params: day_15::PARAMS },
+    Puzzle { day: 16, title: "Packet Decoder", constructor: construct::<day_16::PacketDecoder>, params: &[] },
+    Puzzle { day: 17, title: "Trick Shot", constructor: construct::<day_17::TrickShot>, params: &[] },
+    Puzzle { day: 18, title: "Snailfish", constructor: construct::<day_18::Snailfish>, params: &[] },
+    Puzzle { day: 19, title: "Beacon Scanner", constructor: construct::<day_19::BeaconScaner>, params: &[] },
+    Puzzle { day: 20, title: "Trench Map", constructor: construct::<day_20::TrenchMap>, params: day_20::PARAMS },
+    Puzzle { day: 21, title: "Dirac Dice", constructor: construct::<day_21::DiracDice>, params: day_21::PARAMS },
+    Puzzle { day: 22, title: "Reactor Reboot", constructor: construct::<day_22::ReactorReboot>, params: day_22::PARAMS },
+    Puzzle { day: 23, title: "Amphipod", constructor: construct::<day_23::Amphipods>, params: &[] },
+    Puzzle { day: 24, title: "Arithmetic Logic Unit", constructor: construct::<day_24::ArithemticLogicUnit>, params: &[] },
+    Puzzle { day: 25, title: "Sea Cucumber", constructor: construct::<day_25::SeaCucumber>, params: &[] },
+];
+
+pub fn get(day: u32) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|p| p.day == day)
+}
+
+/// Name of the implementation registered in [`PUZZLES`].
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Alternative implementation of a day, picked with `--variant`.
+pub struct Variant {
+    pub day: u32,
+    pub name: &'static str,
+    pub constructor: Constructor,
+    pub params: &'static [Param],
+}
+
+/// Every alternative implementation, ordered by day number.
+pub static VARIANTS: &[Variant] = &[
+    Variant { day: 15, name: "a-star", constructor: construct::<day_15::a_star::Chiton>, params: &[] },
+    Variant { day: 18, name: "iterative", constructor: construct::<day_18::iterative::Snailfish>, params: &[] },
+    Variant { day: 19, name: "nightly", constructor: construct::<day_19::nightly::BeaconScaner>, params: &[] },
+];
+
+/// Every implementation of the day, the default one first.
+pub fn variants(puzzle: &Puzzle) -> Vec<(&'static str, Puzzle)> {
+    let alternatives = VARIANTS
+        .iter()
+        .filter(|v| v.day == puzzle.day)
+        .map(|v| (v.name, Puzzle { constructor: v.constructor, params: v.params, ..*puzzle }));
+    [(DEFAULT_VARIANT, *puzzle)].into_iter().chain(alternatives).collect()
+}
+
+impl Puzzle {
+    /// The same puzzle solved by the named implementation.
+    pub fn variant(&self, name: &str) -> anyhow::Result<Puzzle> {
+        let variants = variants(self);
+        match variants.iter().find(|(variant, _)| *variant == name) {
+            Some(&(_, puzzle)) => Ok(puzzle),
+            None => {
+                let names: Vec<&str> = variants.iter().map(|(variant, _)| *variant).collect();
+                anyhow::bail!("Day {} has no variant '{}', available are {}", self.day, name, names.join(", "))
+            }
+        }
+    }
+}
+
+pub fn print_list() {
+    for puzzle in PUZZLES {
+        let variants = variants(puzzle);
+        if variants.len() > 1 {
+            let names: Vec<&str> = variants.iter().map(|(name, _)| *name).collect();
+            println!("{:>3}  {} ({})", puzzle.day, puzzle.title, names.join(", "));
+        } else {
+            println!("{:>3}  {}", puzzle.day, puzzle.title);
+        }
+    }
+}
+
+/// Implemented days written as ranges, e.g. "1-25".
+fn available() -> String {
+    let mut ranges: Vec<(u32, u32)> = vec![];
+    for puzzle in PUZZLES {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == puzzle.day => *end = puzzle.day,
+            _ => ranges.push((puzzle.day, puzzle.day)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| if start == end { start.to_string() } else { format!("{}-{}", start, end) })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Days picked on the command line, e.g. `1-5,12,20-`.
+///
+/// A range without a start or an end reaches to the first or last implemented day.
+#[derive(Debug)]
+pub struct Selection {
+    ranges: Vec<(Option<u32>, Option<u32>)>,
+}
+
+impl Selection {
+    pub fn all() -> Self {
+        Selection { ranges: vec![(None, None)] }
+    }
+
+    /// Selected puzzles in day order, erroring on days that aren't implemented.
+    pub fn resolve(&self) -> anyhow::Result<Vec<&'static Puzzle>> {
+        let mut days: BTreeSet<u32> = BTreeSet::new();
+        for &(start, end) in &self.ranges {
+            match (start, end) {
+                (Some(start), Some(end)) => {
+                    if let Some(day) = (start..=end).find(|&day| get(day).is_none()) {
+                        anyhow::bail!("Day {} is not implemented, available days are {}", day, available());
+                    }
+                    days.extend(start..=end);
+                }
+                (start, end) => {
+                    let start = start.unwrap_or(u32::MIN);
+                    let end = end.unwrap_or(u32::MAX);
+                    let open: Vec<u32> = PUZZLES.iter().map(|p| p.day).filter(|day| (start..=end).contains(day)).collect();
+                    if open.is_empty() {
+                        anyhow::bail!("No implemented days in the selection, available days are {}", available());
+                    }
+                    days.extend(open);
+                }
+            }
+        }
+        Ok(days.into_iter().filter_map(get).collect())
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |part: &str| format!("Invalid day selector '{}', expected a day like 12 or a range like 1-5 or 20-", part);
+        let day = |d: &str, part: &str| -> Result<Option<u32>, String> {
+            match d.trim() {
+                "" => Ok(None),
+                d => d.parse().map(Some).map_err(|_| invalid(part)),
+            }
+        };
+
+        let ranges = s
+            .split(',')
+            .map(|part| {
+                let range = match part.split_once('-') {
+                    Some((start, end)) => (day(start, part)?, day(end, part)?),
+                    None => {
+                        let single = day(part, part)?.ok_or_else(|| invalid(part))?;
+                        (Some(single), Some(single))
+                    }
+                };
+                match range {
+                    (Some(start), Some(end)) if start > end => Err(format!("Invalid day range '{}', it ends before it starts", part)),
+                    range => Ok(range),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Selection { ranges })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(selection: &str) -> Vec<u32> {
+        selection.parse::<Selection>().unwrap().resolve().unwrap().iter().map(|p| p.day).collect()
+    }
+
+    #[test]
+    fn resolves_selectors() {
+        assert_eq!(days("1-5,12,20-"), vec![1, 2, 3, 4, 5, 12, 20, 21, 22, 23, 24, 25]);
+        assert_eq!(days("-3,2"), vec![1, 2, 3]);
+        assert_eq!(days("7"), vec![7]);
+    }
+
+    #[test]
+    fn parts_run_in_any_order() {
+        for puzzle in PUZZLES {
+            let path = format!("{}/src/day_{:02}/example.txt", env!("CARGO_MANIFEST_DIR"), puzzle.day);
+            let content = std::fs::read_to_string(path).unwrap();
+            let mut in_order = (puzzle.constructor)(content.trim()).unwrap();
+            let mut reversed = (puzzle.constructor)(content.trim()).unwrap();
+            let part2 = reversed.part2();
+            assert_eq!(reversed.part1(), in_order.part1(), "part 1 of day {}", puzzle.day);
+            assert_eq!(part2, in_order.part2(), "part 2 of day {}", puzzle.day);
+        }
+    }
+
+    #[test]
+    fn declares_the_params_of_every_variant() {
+        for puzzle in PUZZLES {
+            let path = format!("{}/src/day_{:02}/example.txt", env!("CARGO_MANIFEST_DIR"), puzzle.day);
+            let content = std::fs::read_to_string(path).unwrap();
+            for (name, variant) in variants(puzzle) {
+                let event = (variant.constructor)(content.trim()).unwrap();
+                assert_eq!(event.params(), variant.params, "{} of day {}", name, puzzle.day);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        assert!("5-3".parse::<Selection>().is_err());
+        assert!("a".parse::<Selection>().is_err());
+        assert!("1,".parse::<Selection>().is_err());
+        let err = "24-26".parse::<Selection>().unwrap().resolve().err().unwrap();
+        assert_eq!(err.to_string(), "Day 26 is not implemented, available days are 1-25");
+    }
+}
@@ -0,0 +1,34 @@
+//! End to end tests of every day, generated by `build.rs` for each input with stored answers.
+
+use std::path::Path;
+use crate::check::{escape, ExpectedAnswers};
+use crate::inputs::{day_dir, read_input};
+use crate::registry;
+
+macro_rules! regression_test {
+    ($(#[$meta:meta])* $name:ident, $day:expr, $input:expr) => {
+        $(#[$meta])*
+        #[test]
+        fn $name() {
+            check($day, $input);
+        }
+    };
+}
+
+/// Solves the input and compares every part that has an answer in `answers.txt`.
+fn check(day: u32, input: &str) {
+    let puzzle = registry::get(day).unwrap_or_else(|| panic!("Day {} has answers but isn't in the registry", day));
+    let day_dir = day_dir(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src")), day);
+    let expected = ExpectedAnswers::load(&day_dir).unwrap();
+    let content = read_input(None, &day_dir, input).unwrap();
+    let mut event = (puzzle.constructor)(content.trim()).unwrap();
+
+    for part in 1..=2 {
+        if let Some(expected) = expected.get(input, part) {
+            let answer = if part == 1 { event.part1() } else { event.part2() };
+            assert_eq!(escape(&answer), expected, "part {} of day {} on {}", part, day, input);
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/regression.rs"));
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+use std::time::Duration;
+use humantime::format_duration;
+use advent_of_code::answer::Answer;
+use crate::guard::Outcome;
+use crate::memory::Usage;
+
+/// Everything measured while solving a single day.
+pub struct Record {
+    pub day: u32,
+    pub name: &'static str,
+    /// Input file without extension, e.g. `input` or `examples/small`.
+    pub input: String,
+    pub parse_time: Duration,
+    pub part1: Answer,
+    pub time1: Duration,
+    pub status1: Outcome,
+    pub part2: Answer,
+    pub time2: Duration,
+    pub status2: Outcome,
+    /// CPU time spent on the parts, which run on threads of their own.
+    pub cpu_time: Duration,
+    /// Heap usage of `new` and both parts, when built with the `memory` feature.
+    pub memory: [Option<Usage>; 3],
+}
+
+impl Record {
+    pub fn total(&self) -> Duration {
+        self.parse_time + self.time1 + self.time2
+    }
+
+    pub fn answer(&self, part: u8) -> &Answer {
+        if part == 1 { &self.part1 } else { &self.part2 }
+    }
+
+    pub fn status(&self, part: u8) -> &Outcome {
+        if part == 1 { &self.status1 } else { &self.status2 }
+    }
+}
+
+/// Machine readable output formats of the runner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(format!("Unknown format '{}', expected json, csv or markdown", s)),
+        }
+    }
+}
+
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Json => json(records),
+        Format::Csv => csv(records),
+        Format::Markdown => markdown(records),
+    }
+}
+
+fn json(records: &[Record]) -> String {
+    let json_answer = |answer: &Answer| match answer {
+        Answer::Int(value) => value.to_string(),
+        Answer::Empty => "null".to_string(),
+        answer => json_string(&answer.to_string()),
+    };
+    let lines: Vec<String> = records
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"day\": {}, \"name\": {}, \"input\": {}, \"parse_ns\": {}, \"part1\": {}, \"part1_ns\": {}, \"part1_status\": {}, \"part2\": {}, \"part2_ns\": {}, \"part2_status\": {}, \"total_ns\": {}}}",
+                r.day,
+                json_string(r.name),
+                json_string(&r.input),
+                r.parse_time.as_nanos(),
+                json_answer(&r.part1),
+                r.time1.as_nanos(),
+                json_string(&r.status1.to_string()),
+                json_answer(&r.part2),
+                r.time2.as_nanos(),
+                json_string(&r.status2.to_string()),
+                r.total().as_nanos()
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", lines.join(",\n"))
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv(records: &[Record]) -> String {
+    let mut output = String::from("day,name,input,parse_ns,part1,part1_ns,part1_status,part2,part2_ns,part2_status,total_ns\n");
+    for r in records {
+        output.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{}\n",
+            r.day,
+            csv_field(r.name),
+            csv_field(&r.input),
+            r.parse_time.as_nanos(),
+            csv_field(&r.part1.to_string()),
+            r.time1.as_nanos(),
+            csv_field(&r.status1.to_string()),
+            csv_field(&r.part2.to_string()),
+            r.time2.as_nanos(),
+            csv_field(&r.status2.to_string()),
+            r.total().as_nanos()
+        ));
+    }
+    output
+}
+
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn markdown(records: &[Record]) -> String {
+    // Parts that didn't finish show why instead of an answer
+    let cell = |answer: &Answer, status: &Outcome| {
+        if status.is_ok() { markdown_cell(&answer.to_string()) } else { markdown_cell(&status.to_string()) }
+    };
+    let mut output = String::from("| Day | Puzzle | Parse | Part 1 | Time | Part 2 | Time | Total |\n");
+    output.push_str("|----:|--------|------:|-------:|-----:|-------:|-----:|------:|\n");
+    for r in records {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            r.day,
+            if r.input == "input" { markdown_cell(r.name) } else { markdown_cell(&format!("{} ({})", r.name, r.input)) },
+            format_duration(r.parse_time),
+            cell(&r.part1, &r.status1),
+            format_duration(r.time1),
+            cell(&r.part2, &r.status2),
+            format_duration(r.time2),
+            format_duration(r.total())
+        ));
+    }
+    let total: Duration = records.iter().map(|r| r.total()).sum();
+    output.push_str(&format!("| | **Total** | | | | | | {} |\n", format_duration(total)));
+    output
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Record {
+        Record {
+            day: 13,
+            name: "Transparent Origami",
+            input: "input".to_string(),
+            parse_time: Duration::from_nanos(10),
+            part1: Answer::Int(17),
+            time1: Duration::from_nanos(20),
+            status1: Outcome::Ok,
+            part2: Answer::Grid(vec!["#.".to_string(), "\"|".to_string()]),
+            time2: Duration::from_nanos(30),
+            status2: Outcome::Ok,
+            cpu_time: Duration::from_nanos(50),
+            memory: [None; 3],
+        }
+    }
+
+    #[test]
+    fn json_record() {
+        assert_eq!(
+            render(Format::Json, &[record()]),
+            "[\n  {\"day\": 13, \"name\": \"Transparent Origami\", \"input\": \"input\", \"parse_ns\": 10, \"part1\": 17, \"part1_ns\": 20, \"part1_status\": \"OK\", \"part2\": \"#.\\n\\\"|\", \"part2_ns\": 30, \"part2_status\": \"OK\", \"total_ns\": 60}\n]\n"
+        );
+    }
+
+    #[test]
+    fn csv_record() {
+        assert_eq!(
+            render(Format::Csv, &[record()]),
+            "day,name,input,parse_ns,part1,part1_ns,part1_status,part2,part2_ns,part2_status,total_ns\n13,Transparent Origami,input,10,17,20,OK,\"#.\n\"\"|\",30,OK,60\n"
+        );
+    }
+
+    #[test]
+    fn markdown_record() {
+        let table = render(Format::Markdown, &[record()]);
+        let row = table.lines().nth(2).unwrap();
+        assert_eq!(row, "| 13 | Transparent Origami | 10ns | 17 | 20ns | #.<br>\"\\| | 30ns | 60ns |");
+
+        let failed = Record { status2: Outcome::Panic("Puzzle is unsolvable!".to_string()), ..record() };
+        let table = render(Format::Markdown, &[failed]);
+        assert!(table.lines().nth(2).unwrap().contains("| PANIC: Puzzle is unsolvable! |"));
+    }
+}
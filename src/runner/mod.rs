@@ -0,0 +1,618 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::Context;
+use structopt::StructOpt;
+use owo_colors::OwoColorize;
+use humantime::format_duration;
+use advent_of_code::answer::Answer;
+use advent_of_code::ocr;
+use advent_of_code::params::{self, Params};
+use crate::bench::{self, Bench};
+use crate::check::{self, Check, ExpectedAnswers};
+use crate::cross_check;
+use crate::history::{self, Baseline, History};
+use crate::inputs::{day_dir, examples, read_input};
+use crate::registry::{self, Puzzle, Selection};
+use crate::report::{self, Format, Record};
+use crate::solution::{parts, Solution};
+use crate::watch::{self, Watcher};
+use crate::{fetch, get_time, guard, memory, pool, scaffold, visualize};
+
+fn print_record(record: &Record, selected: Option<u8>, variant: Option<&str>) {
+    println!("-----------------------------");
+    let variant = variant.map_or(String::new(), |name| format!(" [{}]", name));
+    if record.input == "input" {
+        println!("Solution for day {}: {}{}", record.day, record.name, variant);
+    } else {
+        println!("Solution for day {}: {} ({}){}", record.day, record.name, record.input, variant);
+    }
+    println!(
+        "Collect data in {}{}",
+        format_duration(record.parse_time).fg_rgb::<255, 63, 128>(),
+        memory_usage(record.memory[0])
+    );
+    for part in parts(selected) {
+        let time = if part == 1 { record.time1 } else { record.time2 };
+        match record.status(part) {
+            guard::Outcome::Ok => print_part(part, record.answer(part), time, record.memory[part as usize]),
+            status => println!("Part {}: {}", part, status.red()),
+        }
+    }
+}
+
+fn print_part(part: u8, answer: &Answer, time: Duration, memory: Option<memory::Usage>) {
+    if answer.is_multiline() {
+        println!(
+            "Part {} in {}{}:",
+            part,
+            format_duration(time).fg_rgb::<255, 63, 128>(),
+            memory_usage(memory)
+        );
+        for line in answer.to_string().lines() {
+            println!("\t{}", line.fg_rgb::<100, 252, 218>());
+        }
+        // Grids are answered by days which couldn't read the letters drawn on them, the
+        // glyphs themselves were just printed as part of the grid
+        if let Answer::Grid(rows) = answer {
+            if let Err(err) = ocr::recognize_grid(rows) {
+                println!("\t{}", err.to_string().lines().next().unwrap_or_default().red());
+            }
+        }
+    } else {
+        println!(
+            "Part {}: {} in {}{}",
+            part,
+            answer.fg_rgb::<100, 252, 218>(),
+            format_duration(time).fg_rgb::<255, 63, 128>(),
+            memory_usage(memory)
+        );
+    }
+}
+
+fn memory_usage(memory: Option<memory::Usage>) -> String {
+    memory.map_or(String::new(), |usage| format!(" ({})", usage))
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Downloads the puzzle input of each day which doesn't have one yet
+    Fetch {
+        #[structopt(value_name = "DAYS", help = "Days to download, e.g. 1-5,12,20- [default: all]")]
+        days: Option<Selection>,
+
+        #[structopt(
+            long,
+            parse(from_os_str),
+            value_name = "PATH",
+            help = "File holding the session cookie when AOC_SESSION isn't set [default: ~/.config/advent_of_code/session]"
+        )]
+        session_file: Option<PathBuf>,
+
+        #[structopt(long, value_name = "URL", env = "AOC_BASE_URL", default_value = fetch::BASE_URL, help = "Server to download from")]
+        base_url: String,
+    },
+
+    /// Creates the module, example and answers of a day and registers it
+    NewDay {
+        #[structopt(value_name = "N", help = "Day of the calendar, 1 to 25")]
+        day: u32,
+
+        #[structopt(value_name = "NAME", help = "Title of the puzzle, which also names the solver type")]
+        name: String,
+    },
+}
+
+/// Command line of the `advent_of_code` binary.
+#[derive(StructOpt)]
+pub struct Cli {
+    #[structopt(subcommand)]
+    command: Option<Command>,
+
+    #[structopt(value_name = "DAYS", help = "Days to run, e.g. 1-5,12,20- [default: all]")]
+    days: Option<Selection>,
+
+    #[structopt(long, help = "Lists the implemented days")]
+    list: bool,
+
+    #[structopt(
+        short,
+        long,
+        value_name = "NAME",
+        require_equals = true,
+        help = "Uses example file provided by AOC, or examples/NAME.txt with --example=NAME"
+    )]
+    example: Option<Option<String>>,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["example", "input", "bench"],
+        help = "Runs every example of the selected days and compares them with answers.txt"
+    )]
+    all_examples: bool,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        value_name = "PATH",
+        requires = "days",
+        conflicts_with_all = &["example", "check", "record"],
+        help = "Reads the puzzle input from this file instead, - reads it from stdin"
+    )]
+    input: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        value_name = "DIR",
+        env = "AOC_INPUTS_DIR",
+        default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+        help = "Directory holding a day_{NN} folder with input, example and answers files for each day"
+    )]
+    inputs_dir: PathBuf,
+
+    #[structopt(
+        long,
+        possible_values = &["1", "2"],
+        conflicts_with = "bench",
+        help = "Solves only this part of each day"
+    )]
+    part: Option<u8>,
+
+    #[structopt(long, conflicts_with = "record", help = "Compares answers with the ones stored in answers.txt")]
+    check: bool,
+
+    #[structopt(long, help = "Stores answers into answers.txt")]
+    record: bool,
+
+    #[structopt(
+        long,
+        value_name = "N",
+        conflicts_with_all = &["check", "record"],
+        help = "Runs each day N times and reports timing statistics"
+    )]
+    bench: Option<usize>,
+
+    #[structopt(
+        long,
+        value_name = "N",
+        requires = "bench",
+        help = "Number of unmeasured runs before benchmarking [default: 0]"
+    )]
+    warmup: Option<usize>,
+
+    #[structopt(
+        long,
+        possible_values = &["json", "csv", "markdown"],
+        conflicts_with_all = &["check", "bench"],
+        help = "Prints one structured record per day instead of the text report"
+    )]
+    format: Option<Format>,
+
+    #[structopt(
+        long,
+        value_name = "N",
+        conflicts_with = "bench",
+        help = "Solves up to N days at the same time [default: 1]"
+    )]
+    jobs: Option<usize>,
+
+    #[structopt(
+        long,
+        value_name = "DURATION",
+        parse(try_from_str = humantime::parse_duration),
+        conflicts_with = "bench",
+        help = "Gives up on a part after running this long, e.g. 30s or 500ms"
+    )]
+    timeout: Option<Duration>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        value_name = "PATH",
+        env = "AOC_HISTORY",
+        default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/history.csv"),
+        help = "File which release builds append each day's timings to"
+    )]
+    history: PathBuf,
+
+    #[structopt(long, help = "Doesn't append the timings of this run to the history")]
+    no_history: bool,
+
+    #[structopt(
+        long,
+        value_name = "BASELINE",
+        possible_values = &["previous", "best"],
+        conflicts_with_all = &["bench", "format"],
+        help = "Compares each day's time with the previous or best run in the history"
+    )]
+    compare: Option<Baseline>,
+
+    #[structopt(
+        long,
+        value_name = "PERCENT",
+        default_value = "10",
+        help = "Flags days which got slower than this with --compare"
+    )]
+    threshold: f64,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["bench", "format", "check", "record", "all-examples", "compare", "jobs"],
+        help = "Animates the simulation of the days which can show it"
+    )]
+    visualize: bool,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["bench", "format", "check", "record", "all-examples", "compare", "jobs", "visualize"],
+        help = "Solves the days again whenever the files they read change"
+    )]
+    watch: bool,
+
+    #[structopt(
+        long = "param",
+        value_name = "NAME=VALUE",
+        number_of_values = 1,
+        parse(try_from_str = params::parse_assignment),
+        help = "Changes a constant of the selected days, e.g. steps=500 for day 11"
+    )]
+    params: Vec<(String, usize)>,
+
+    #[structopt(long, value_name = "NAME", help = "Runs this alternative implementation of the days, see --list")]
+    variant: Option<String>,
+
+    #[structopt(
+        long,
+        conflicts_with_all = &["variant", "bench", "format", "check", "record", "all-examples", "compare", "jobs", "visualize", "watch"],
+        help = "Runs every implementation of the days which have several and compares their answers"
+    )]
+    cross_check: bool,
+
+    #[structopt(long, value_name = "N", default_value = "10", help = "Frames per second of --visualize")]
+    fps: f64,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        value_name = "DIR",
+        requires = "visualize",
+        help = "Writes the frames of --visualize to text files in DIR instead"
+    )]
+    frames_dir: Option<PathBuf>,
+}
+
+/// Runs what the command line asks for, failing when a day couldn't be solved or checked.
+pub fn run(args: Cli) -> Result<(), Box<dyn Error>> {
+    let main_file = match &args.example {
+        None => "input".to_string(),
+        Some(None) => "example".to_string(),
+        Some(Some(name)) => format!("examples/{}", name),
+    };
+    let source = |main_file: &str| match &args.input {
+        Some(path) if path == Path::new("-") => "stdin".to_string(),
+        Some(path) => path.display().to_string(),
+        None => format!("{} file", main_file),
+    };
+
+    if args.list {
+        registry::print_list();
+        return Ok(());
+    }
+
+    if let Some(Command::NewDay { day, name }) = &args.command {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        for path in scaffold::new_day(&src, *day, name)? {
+            println!("Wrote {}", path.display());
+        }
+        println!("Run `fetch {}` to download the input", day);
+        return Ok(());
+    }
+
+    if let Some(Command::Fetch { days, session_file, base_url }) = args.command {
+        let session = fetch::session(session_file.or_else(fetch::default_session_file).as_deref())?;
+        let fetcher = fetch::Fetcher::new(fetch::Agent::new(), &base_url, &session, &args.inputs_dir);
+        for puzzle in days.unwrap_or_else(Selection::all).resolve()? {
+            match fetcher.fetch(puzzle.day)? {
+                fetch::Fetched::Cached(path) => println!("Day {} is cached in {}", puzzle.day, path.display()),
+                fetch::Fetched::Downloaded(path) => println!("Downloaded day {} to {}", puzzle.day, path.display()),
+            }
+        }
+        return Ok(());
+    }
+
+    let puzzles: Vec<Puzzle> = args.days.unwrap_or_else(Selection::all).resolve()?.into_iter().copied().collect();
+    let puzzles: Vec<Puzzle> = match &args.variant {
+        Some(name) => puzzles.iter().map(|puzzle| puzzle.variant(name)).collect::<anyhow::Result<_>>()?,
+        None => puzzles,
+    };
+    if args.input.is_some() && puzzles.len() != 1 {
+        return Err(anyhow::anyhow!("--input needs a single day").into());
+    }
+    let params = Params::new(args.params.clone());
+    // Fails before solving anything for names a day doesn't declare
+    for puzzle in &puzzles {
+        params.validate(puzzle.params).with_context(|| format!("Can't set the parameters of day {}", puzzle.day))?;
+    }
+    let check = args.check || args.all_examples;
+    let mut cpu_time = Duration::ZERO;
+    let mut checks: Vec<Check> = vec![];
+    let mut bench_totals: Vec<Duration> = vec![];
+    let mut records: Vec<Record> = vec![];
+    let mut malformed: Vec<u32> = vec![];
+    let mut unfinished: Vec<u32> = vec![];
+
+    if args.bench == Some(0) {
+        return Err(anyhow::anyhow!("Benchmark needs at least one run").into());
+    }
+    if args.jobs == Some(0) {
+        return Err(anyhow::anyhow!("Needs at least one job").into());
+    }
+
+    if args.visualize {
+        if !(args.fps > 0.0 && args.fps.is_finite()) {
+            return Err(anyhow::anyhow!("Needs a positive frame rate").into());
+        }
+        let output = match &args.frames_dir {
+            Some(dir) => visualize::Output::Directory(dir.clone()),
+            None => visualize::Output::Terminal(Duration::from_secs_f64(1.0 / args.fps)),
+        };
+        for puzzle in &puzzles {
+            let content = read_input(args.input.as_deref(), &day_dir(&args.inputs_dir, puzzle.day), &main_file)?;
+            let mut event = match (puzzle.constructor)(content.trim()).and_then(|mut event| {
+                params.apply(event.as_mut())?;
+                Ok(event)
+            }) {
+                Ok(event) => event,
+                Err(err) => {
+                    report_malformed(puzzle.day, &source(&main_file), err);
+                    malformed.push(puzzle.day);
+                    continue;
+                }
+            };
+            let Some(visualization) = event.visualization() else {
+                println!("Day {} has nothing to visualize", puzzle.day);
+                continue;
+            };
+            for part in parts(args.part) {
+                let frames = visualize::play(visualization, puzzle.day, part, &output)?;
+                if let (Some(dir), 1..) = (&args.frames_dir, frames) {
+                    println!("Wrote {} frames of day {} part {} to {}", frames, puzzle.day, part, dir.display());
+                }
+            }
+        }
+        if !malformed.is_empty() {
+            let days: Vec<String> = malformed.iter().map(|d| d.to_string()).collect();
+            return Err(anyhow::anyhow!("Malformed input for day(s) {}", days.join(", ")).into());
+        }
+        return Ok(());
+    }
+
+    if args.cross_check {
+        let puzzles: Vec<&Puzzle> = puzzles.iter().filter(|p| registry::variants(p).len() > 1).collect();
+        if puzzles.is_empty() {
+            return Err(anyhow::anyhow!("None of the selected days has an alternative implementation").into());
+        }
+        let mut disagreeing: Vec<u32> = vec![];
+        for puzzle in puzzles {
+            let content = read_input(args.input.as_deref(), &day_dir(&args.inputs_dir, puzzle.day), &main_file)?;
+            let runs = match cross_check::run(puzzle, &main_file, content.trim(), &params, args.part, args.timeout) {
+                Ok(runs) => runs,
+                Err(err) => {
+                    report_malformed(puzzle.day, &source(&main_file), err);
+                    malformed.push(puzzle.day);
+                    continue;
+                }
+            };
+            if cross_check::print_table(&runs, args.part) > 0 {
+                disagreeing.push(puzzle.day);
+            }
+        }
+        let mut errors: Vec<String> = vec![];
+        if !disagreeing.is_empty() {
+            let days: Vec<String> = disagreeing.iter().map(|d| d.to_string()).collect();
+            errors.push(format!("Variants disagree on day(s) {}", days.join(", ")));
+        }
+        if !malformed.is_empty() {
+            let days: Vec<String> = malformed.iter().map(|d| d.to_string()).collect();
+            errors.push(format!("Malformed input for day(s) {}", days.join(", ")));
+        }
+        if !errors.is_empty() {
+            return Err(anyhow::anyhow!(errors.join("; ")).into());
+        }
+        return Ok(());
+    }
+
+    if args.watch {
+        if args.input.as_deref() == Some(Path::new("-")) {
+            return Err(anyhow::anyhow!("--watch can't poll stdin, pass a file with --input").into());
+        }
+        // Every file of a day is polled, though only the selected one gets solved again
+        let files = puzzles.iter().flat_map(|p| {
+            let day_files = watch::day_files(&day_dir(&args.inputs_dir, p.day), &main_file, args.input.as_deref());
+            day_files.into_iter().map(move |path| (p.day, path))
+        });
+        let mut watcher = Watcher::new(files.collect());
+        if watcher.is_empty() {
+            return Err(anyhow::anyhow!("--watch has no files to poll for the selected days").into());
+        }
+        let mut previous: BTreeMap<u32, Record> = BTreeMap::new();
+        let mut days: Vec<u32> = puzzles.iter().map(|p| p.day).collect();
+        loop {
+            for puzzle in puzzles.iter().filter(|p| days.contains(&p.day)) {
+                let content = match read_input(args.input.as_deref(), &day_dir(&args.inputs_dir, puzzle.day), &main_file) {
+                    Ok(content) => content,
+                    Err(err) => {
+                        eprintln!("{} {:#}", format!("Day {}:", puzzle.day).red(), err);
+                        continue;
+                    }
+                };
+                let record = match Solution::new(puzzle.constructor, content.trim()).and_then(|s| s.with_params(&params)) {
+                    Ok(mut solution) => solution.get_result(puzzle, &main_file, args.part, args.timeout),
+                    Err(err) => {
+                        report_malformed(puzzle.day, &source(&main_file), err);
+                        continue;
+                    }
+                };
+                print_record(&record, args.part, args.variant.as_deref());
+                if let Some(before) = previous.get(&puzzle.day) {
+                    for line in watch::diff(before, &record, parts(args.part)) {
+                        println!("{}", line.yellow());
+                    }
+                }
+                previous.insert(puzzle.day, record);
+            }
+            println!("-----------------------------");
+            println!("Watching {} file(s) for changes, press Ctrl-C to stop", watcher.len());
+            days = watcher.wait();
+        }
+    }
+
+    let mut wall_time = Duration::ZERO;
+    if let Some(runs) = args.bench {
+        for puzzle in &puzzles {
+            let content = read_input(args.input.as_deref(), &day_dir(&args.inputs_dir, puzzle.day), &main_file)?;
+            let benched = Bench::run(puzzle.day, puzzle.constructor, content.trim(), &params, runs, args.warmup.unwrap_or(0), args.timeout);
+            let bench = match benched {
+                Ok(Ok(bench)) => bench,
+                Ok(Err((part, outcome))) => {
+                    eprintln!("{} part {}: {}", format!("Benchmark of day {} stopped at", puzzle.day).red(), part, outcome);
+                    unfinished.push(puzzle.day);
+                    continue;
+                }
+                Err(err) => {
+                    report_malformed(puzzle.day, &source(&main_file), err);
+                    malformed.push(puzzle.day);
+                    continue;
+                }
+            };
+            bench.print(puzzle.day);
+            bench_totals = bench.totals().iter().enumerate()
+                .map(|(i, total)| *total + bench_totals.get(i).copied().unwrap_or_default())
+                .collect();
+        }
+    } else {
+        // Every input to solve, keyed by the file name without extension
+        let mut inputs: Vec<(&Puzzle, String)> = vec![];
+        for puzzle in &puzzles {
+            if args.all_examples {
+                let examples = examples(&day_dir(&args.inputs_dir, puzzle.day))?;
+                inputs.extend(examples.into_iter().map(|name| (puzzle, name)));
+            } else {
+                inputs.push((puzzle, main_file.clone()));
+            }
+        }
+        let inputs: Vec<(&Puzzle, &str)> = inputs.iter().map(|(puzzle, name)| (*puzzle, name.as_str())).collect();
+
+        let solve = |(puzzle, main_file): (&Puzzle, &str)| -> anyhow::Result<anyhow::Result<Record>> {
+            let content = read_input(args.input.as_deref(), &day_dir(&args.inputs_dir, puzzle.day), main_file)?;
+            Ok(Solution::new(puzzle.constructor, content.trim())
+                .and_then(|solution| solution.with_params(&params))
+                .map(|mut solution| solution.get_result(puzzle, main_file, args.part, args.timeout)))
+        };
+
+        let handle = |(puzzle, main_file): (&Puzzle, &str), solved: anyhow::Result<anyhow::Result<Record>>, time: Duration| -> anyhow::Result<()> {
+            let day = puzzle.day;
+            cpu_time += time;
+            let record = match solved? {
+                Ok(record) => record,
+                Err(err) => {
+                    report_malformed(day, &source(main_file), err);
+                    if malformed.last() != Some(&day) {
+                        malformed.push(day);
+                    }
+                    return Ok(());
+                }
+            };
+            cpu_time += record.cpu_time;
+            if !(record.status1.is_ok() && record.status2.is_ok()) && unfinished.last() != Some(&day) {
+                unfinished.push(day);
+            }
+
+            if check || args.record {
+                let mut expected = ExpectedAnswers::load(&day_dir(&args.inputs_dir, day))?;
+                if args.record {
+                    for part in expected.record(&record, parts(args.part)) {
+                        eprintln!("{} part {} didn't finish, keeping its stored answer", format!("Day {}:", day).red(), part);
+                    }
+                    expected.save()?;
+                } else {
+                    for part in parts(args.part) {
+                        checks.push(Check::new(day, main_file, part, expected.get(main_file, part), record.answer(part)));
+                    }
+                }
+            }
+
+            if args.format.is_none() {
+                print_record(&record, args.part, args.variant.as_deref());
+            }
+            records.push(record);
+            Ok(())
+        };
+
+        let (result, time) = get_time(|| pool::run(&inputs, args.jobs.unwrap_or(1), solve, handle));
+        result?;
+        wall_time = time;
+    }
+
+    if args.bench.is_some() {
+        bench::print_total(&bench_totals);
+    } else if let Some(format) = args.format {
+        print!("{}", report::render(format, &records));
+    } else {
+        println!("-----------------------------");
+        println!(
+            "Wall-clock time: {}",
+            format_duration(wall_time).fg_rgb::<255, 63, 128>()
+        );
+        println!(
+            "Summed CPU time: {}",
+            format_duration(cpu_time).fg_rgb::<255, 63, 128>()
+        );
+    }
+
+    let mut errors: Vec<String> = vec![];
+    // Partial runs, alternative implementations and debug builds would only skew the history
+    let finished: Vec<Record> = records.into_iter().filter(|r| r.status1.is_ok() && r.status2.is_ok()).collect();
+    let keep_history = !args.no_history
+        && args.part.is_none()
+        && args.input.is_none()
+        && args.variant.is_none()
+        && !cfg!(debug_assertions);
+    if args.compare.is_some() || (keep_history && !finished.is_empty()) {
+        let history = History::load(&args.history)?;
+        if let Some(baseline) = args.compare {
+            let slower = history::print_comparison(&history, &finished, baseline, args.threshold);
+            if slower > 0 {
+                errors.push(format!("{} day(s) got more than {}% slower", slower, args.threshold));
+            }
+        }
+        if keep_history {
+            history.append(&finished)?;
+        }
+    }
+    if check {
+        let failed = check::print_table(&checks);
+        if failed > 0 {
+            errors.push(format!("{} answer(s) didn't match", failed));
+        }
+    }
+    if !malformed.is_empty() {
+        let days: Vec<String> = malformed.iter().map(|d| d.to_string()).collect();
+        errors.push(format!("Malformed input for day(s) {}", days.join(", ")));
+    }
+    if !unfinished.is_empty() {
+        let days: Vec<String> = unfinished.iter().map(|d| d.to_string()).collect();
+        errors.push(format!("Panicked or timed out on day(s) {}", days.join(", ")));
+    }
+    if !errors.is_empty() {
+        return Err(anyhow::anyhow!(errors.join("; ")).into());
+    }
+
+    Ok(())    
+}
+
+fn report_malformed(day: u32, source: &str, err: anyhow::Error) {
+    eprintln!("{} {:#}", format!("Could not parse {} for day {}:", source, day).red(), err);
+}
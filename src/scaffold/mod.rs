@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Context;
+use crate::inputs::day_dir;
+
+const MODULE: &str = r#"use crate::answer::Answer;
+use crate::parse::Input;
+
+#[allow(dead_code)]
+pub struct {type} {
+    lines: Vec<String>,
+}
+
+impl crate::Advent for {type} {
+    fn new(data: &str) -> anyhow::Result<Self> {
+        let input = Input::new(data);
+        Ok({type} { lines: input.lines().map(str::to_string).collect() })
+    }
+
+    fn part1(&mut self) -> Answer {
+        Answer::Empty
+    }
+
+    fn part2(&mut self) -> Answer {
+        Answer::Empty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advent;
+
+    #[test]
+    fn example() {
+        let mut day = {type}::new(include_str!("example.txt")).unwrap();
+        assert_eq!(day.part1(), Answer::Empty);
+        assert_eq!(day.part2(), Answer::Empty);
+    }
+}
+"#;
+
+/// Solver type named after the puzzle title, e.g. `TheTreacheryOfWhales`.
+fn type_name(title: &str) -> anyhow::Result<String> {
+    let name: String = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase()).into_iter().chain(chars)
+        })
+        .collect();
+    match name.chars().next() {
+        Some(first) if first.is_ascii_alphabetic() => Ok(name),
+        _ => anyhow::bail!("Can't name a type after '{}', it needs to start with a letter", title),
+    }
+}
+
+/// Creates `day_NN` in the source directory with a solver skeleton, an empty example and
+/// answers, and registers it in `lib.rs` and the registry. Returns the touched files.
+pub fn new_day(src: &Path, day: u32, title: &str) -> anyhow::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("Day {} is not in the calendar, expected 1 to 25", day);
+    }
+    let dir = day_dir(src, day);
+    if dir.exists() {
+        anyhow::bail!("{} already exists", dir.display());
+    }
+    let module = format!("day_{:02}", day);
+    let name = type_name(title)?;
+
+    // Edit both files before writing anything, so a layout we don't understand leaves no traces
+    let lib_path = src.join("lib.rs");
+    let lib = read(&lib_path)?;
+    let lib = insert_line(&lib, "pub mod day_", &format!("pub mod {};", module))
+        .with_context(|| format!("Could not find the day modules in {}", lib_path.display()))?;
+
+    let registry_path = src.join("registry/mod.rs");
+    let registry = read(&registry_path)?;
+    let entry = format!(
+        "    Puzzle {{ day: {}, title: {:?}, constructor: construct::<{}::{}>, params: &[] }},",
+        day, title, module, name
+    );
+    let registry = insert_line(&registry, "    Puzzle { day: ", &entry)
+        .and_then(|registry| import_module(&registry, &module))
+        .with_context(|| format!("Could not find the puzzle list in {}", registry_path.display()))?;
+
+    fs::create_dir_all(&dir).with_context(|| format!("Could not create {}", dir.display()))?;
+    let files = [
+        (dir.join("mod.rs"), MODULE.replace("{type}", &name)),
+        (dir.join("example.txt"), String::new()),
+        (dir.join("answers.txt"), String::new()),
+        (lib_path, lib),
+        (registry_path, registry),
+    ];
+    for (path, content) in &files {
+        fs::write(path, content).with_context(|| format!("Could not write {}", path.display()))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))
+}
+
+/// Inserts the line into the block of lines starting with `prefix`, keeping the block sorted.
+fn insert_line(content: &str, prefix: &str, line: &str) -> anyhow::Result<String> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let block: Vec<usize> = (0..lines.len()).filter(|&i| lines[i].starts_with(prefix)).collect();
+    let (Some(&first), Some(&last)) = (block.first(), block.last()) else {
+        anyhow::bail!("No line starts with '{}'", prefix.trim());
+    };
+    // Entries are ordered by day, which sorts the same as the zero padded module names
+    let key = |line: &str| sort_key(line.trim_start_matches(prefix));
+    let at = (first..=last).find(|&i| key(lines[i]) > key(line)).unwrap_or(last + 1);
+    lines.insert(at, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn sort_key(rest: &str) -> u32 {
+    rest.chars().take_while(char::is_ascii_digit).collect::<String>().parse().unwrap_or(0)
+}
+
+/// Adds the module to the `use advent_of_code::{day_01, ...};` list of the registry.
+fn import_module(registry: &str, module: &str) -> anyhow::Result<String> {
+    let start = registry.find("use advent_of_code::{\n    day_").context("No import of the day modules")?;
+    let end = start + registry[start..].find("};").context("Unterminated import of the day modules")? + 2;
+    let mut modules: Vec<&str> = registry[start + "use advent_of_code::{".len()..end - 2]
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect();
+    modules.push(module);
+    modules.sort();
+
+    let mut import = String::from("use advent_of_code::{\n");
+    for chunk in modules.chunks(13) {
+        import.push_str(&format!("    {},\n", chunk.join(", ")));
+    }
+    import.push_str("};");
+    Ok(format!("{}{}{}", &registry[..start], import, &registry[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod answer;\n\npub mod day_01;\npub mod day_03;\n\nuse answer::Answer;\n";
+    const REGISTRY: &str = r#"use crate::{construct, Constructor};
+use advent_of_code::{
+    day_01, day_03,
+};
+
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle { day: 1, title: "Sonar Sweep", constructor: construct::<day_01::SonarSweep>, params: &[] },
+    Puzzle { day: 3, title: "Binary Diagnostic", constructor: construct::<day_03::BinaryDiagnostic>, params: &[] },
+];
+"#;
+
+    #[test]
+    fn names_types_after_titles() {
+        assert_eq!(type_name("The Treachery of Whales").unwrap(), "TheTreacheryOfWhales");
+        assert_eq!(type_name("Dive!").unwrap(), "Dive");
+        assert!(type_name("2048").is_err());
+    }
+
+    #[test]
+    fn registers_the_day() {
+        let src = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        fs::create_dir_all(src.join("registry")).unwrap();
+        fs::write(src.join("lib.rs"), LIB).unwrap();
+        fs::write(src.join("registry/mod.rs"), REGISTRY).unwrap();
+
+        new_day(&src, 2, "Dive!").unwrap();
+        assert_eq!(
+            fs::read_to_string(src.join("lib.rs")).unwrap(),
+            "pub mod answer;\n\npub mod day_01;\npub mod day_02;\npub mod day_03;\n\nuse answer::Answer;\n"
+        );
+        let registry = fs::read_to_string(src.join("registry/mod.rs")).unwrap();
+        assert!(registry.contains("use advent_of_code::{\n    day_01, day_02, day_03,\n};"));
+        assert!(registry.contains(concat!(
+            "construct::<day_01::SonarSweep>, params: &[] },\n",
+            "    Puzzle { day: 2, title: \"Dive!\", constructor: construct::<day_02::Dive>, params: &[] },\n",
+            "    Puzzle { day: 3,"
+        )));
+        assert!(fs::read_to_string(src.join("day_02/mod.rs")).unwrap().contains("impl crate::Advent for Dive {"));
+        assert!(new_day(&src, 2, "Dive!").is_err());
+        fs::remove_dir_all(src).unwrap();
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::hash_map::DefaultHasher;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// States which keep repeating instead of reaching what a simulation was run until.
+#[derive(Debug, PartialEq)]
+pub struct Cycle {
+    /// Steps taken when the repetition was noticed.
+    pub after: u64,
+    /// Steps after which the state repeats.
+    pub length: u64,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the state repeats every {} steps, found after {} steps", self.length, self.after)
+    }
+}
+
+impl Error for Cycle {}
+
+/// A state advanced one step at a time, like the lanternfish of day 6 or the octopuses of day 11.
+///
+/// Running many steps jumps ahead once the states repeat, so a simulation which settles into
+/// a cycle can be run for e.g. 10^12 steps.
+pub trait Simulation: Clone + Eq + Hash {
+    /// Advances the state by one step.
+    fn step(&mut self);
+
+    /// Advances the state by one step, returning whether it changed. Simulations which can
+    /// tell without comparing the whole state override this.
+    fn step_changed(&mut self) -> bool {
+        let before = self.clone();
+        self.step();
+        *self != before
+    }
+
+    /// Hash of the state, which is compared before the states themselves.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Advances the state by `steps` steps.
+    fn run(&mut self, steps: u64) {
+        advance(self, steps, None::<fn(&Self) -> u64>);
+    }
+
+    /// Advances the state by `steps` steps, returning the sum of `value` of the state after each.
+    fn run_summing(&mut self, steps: u64, value: impl Fn(&Self) -> u64) -> u64 {
+        advance(self, steps, Some(value))
+    }
+
+    /// Steps until `done` holds for the state, returning how many steps it took, or the cycle
+    /// the states fall into without `done` ever holding.
+    fn run_until(&mut self, done: impl Fn(&Self) -> bool) -> Result<u64, Cycle> {
+        if done(self) {
+            return Ok(0);
+        }
+        run_detecting(self, |state| {
+            state.step();
+            !done(state)
+        })
+    }
+
+    /// Steps until a step leaves the state as it was, returning how many steps it took including
+    /// that one, or the cycle the states fall into instead.
+    fn run_until_stable(&mut self) -> Result<u64, Cycle> {
+        run_detecting(self, |state| state.step_changed())
+    }
+}
+
+/// Advances the state by `steps` steps, summing `value` of the state after each when given.
+fn advance<S: Simulation>(state: &mut S, steps: u64, value: Option<impl Fn(&S) -> u64>) -> u64 {
+    // Brent's algorithm: every state is compared with a snapshot, which moves on to the
+    // current state whenever the distance to it reaches the next power of two
+    let mut snapshot = (state.fingerprint(), state.clone());
+    let (mut power, mut distance) = (1, 0);
+    // Values since the snapshot, which make up a whole cycle once the state matches it
+    let mut values: Vec<u64> = vec![];
+    let mut sum = 0;
+    for done in 1..=steps {
+        state.step();
+        if let Some(value) = &value {
+            let current = value(state);
+            sum += current;
+            values.push(current);
+        }
+        distance += 1;
+
+        let fingerprint = state.fingerprint();
+        if fingerprint == snapshot.0 && *state == snapshot.1 {
+            let remaining = steps - done;
+            let (cycles, rest) = (remaining / distance, (remaining % distance) as usize);
+            if value.is_some() {
+                sum += cycles * values.iter().sum::<u64>() + values[..rest].iter().sum::<u64>();
+            }
+            for _ in 0..rest {
+                state.step();
+            }
+            return sum;
+        }
+        if distance == power {
+            snapshot = (fingerprint, state.clone());
+            power *= 2;
+            distance = 0;
+            values.clear();
+        }
+    }
+    sum
+}
+
+/// Steps while `step` returns true, returning how many times it was called, or the cycle found
+/// with the same snapshots as [`advance`].
+fn run_detecting<S: Simulation>(state: &mut S, mut step: impl FnMut(&mut S) -> bool) -> Result<u64, Cycle> {
+    let mut snapshot = (state.fingerprint(), state.clone());
+    let (mut power, mut distance) = (1, 0);
+    let mut steps = 0;
+    loop {
+        steps += 1;
+        if !step(state) {
+            return Ok(steps);
+        }
+        distance += 1;
+
+        let fingerprint = state.fingerprint();
+        if fingerprint == snapshot.0 && *state == snapshot.1 {
+            return Err(Cycle { after: steps, length: distance });
+        }
+        if distance == power {
+            snapshot = (fingerprint, state.clone());
+            power *= 2;
+            distance = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::day_11::DumboOctopus;
+    use crate::params::Params;
+    use crate::Advent;
+
+    /// Counts up to `limit`, then starts over from `restart`.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct Counter {
+        value: u64,
+        restart: u64,
+        limit: u64,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.value = if self.value == self.limit { self.restart } else { self.value + 1 };
+        }
+    }
+
+    #[test]
+    fn jumps_ahead_in_cycles() {
+        let counter = Counter { value: 0, restart: 3, limit: 9 };
+        for steps in [0, 1, 5, 10, 17, 100, 1234] {
+            let (mut jumping, mut stepping) = (counter.clone(), counter.clone());
+            let sum = jumping.run_summing(steps, |c| c.value);
+            let mut expected = 0;
+            for _ in 0..steps {
+                stepping.step();
+                expected += stepping.value;
+            }
+            assert_eq!((jumping.value, sum), (stepping.value, expected), "{} steps", steps);
+        }
+
+        let mut far = counter;
+        far.run(1_000_000_000_000);
+        assert_eq!(far.value, 3 + (1_000_000_000_000 - 3) % 7);
+    }
+
+    #[test]
+    fn runs_until_stable() {
+        let mut counter = Counter { value: 0, restart: 5, limit: 5 };
+        assert_eq!(counter.run_until_stable(), Ok(6));
+        assert_eq!(counter.value, 5);
+        assert_eq!(Counter { value: 0, restart: 0, limit: 5 }.run_until(|c| c.value == 4), Ok(4));
+    }
+
+    #[test]
+    fn finds_cycles_which_never_finish() {
+        let counter = Counter { value: 0, restart: 3, limit: 9 };
+        assert_eq!(counter.clone().run_until_stable().map_err(|cycle| cycle.length), Err(7));
+        assert_eq!(counter.clone().run_until(|c| c.value == 10).map_err(|cycle| cycle.length), Err(7));
+        assert_eq!(counter.clone().run_until(|c| c.value == 8), Ok(8));
+    }
+
+    #[test]
+    #[should_panic(expected = "never stop moving, the state repeats every 2 steps")]
+    fn reports_cucumbers_which_never_stop() {
+        crate::day_25::SeaCucumber::new(">.\n..").unwrap().part1();
+    }
+
+    #[test]
+    fn runs_octopuses_for_a_trillion_steps() {
+        let flashes = |steps: usize| {
+            let mut octopuses = DumboOctopus::new(include_str!("../day_11/example.txt")).unwrap();
+            Params::new(vec![("steps".to_string(), steps)]).apply(&mut octopuses).unwrap();
+            octopuses.part1()
+        };
+        // All octopuses flash together on step 195, and again every 10 steps after it
+        let Answer::Int(synced) = flashes(195) else { panic!("expected a number of flashes") };
+        assert_eq!(flashes(1_000_000_000_000), Answer::Int(synced + 100 * ((1_000_000_000_000 - 195) / 10)));
+    }
+}
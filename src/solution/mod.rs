@@ -0,0 +1,86 @@
+use std::time::Duration;
+use crate::memory;
+use crate::guard;
+use advent_of_code::params::Params;
+use crate::registry::Puzzle;
+use crate::report::Record;
+use advent_of_code::Advent;
+use crate::{get_time, Constructor};
+
+/// A day constructed from its input, solving the parts on their own threads.
+pub struct Solution<'a> {
+    event: Option<Box<dyn Advent>>,
+    time: Duration,
+    memory: Option<memory::Usage>,
+    constructor: Constructor,
+    content: &'a str,
+    params: Option<&'a Params>,
+}
+
+impl<'a> Solution<'a> {
+    pub fn new(constructor: Constructor, content: &'a str) -> anyhow::Result<Self> {
+        let ((event, memory), time) = get_time(|| memory::measure(|| constructor(content)));
+
+        Ok(Solution { event: Some(event?), time, memory, constructor, content, params: None })
+    }
+
+    /// Sets the `--param` values on the day, and on the instances rebuilt after a panic.
+    pub fn with_params(mut self, params: &'a Params) -> anyhow::Result<Self> {
+        if let Some(event) = self.event.as_mut() {
+            params.apply(event.as_mut())?;
+        }
+        self.params = Some(params);
+        Ok(self)
+    }
+
+    /// Solves the selected parts, the skipped one is left empty.
+    pub fn get_result(&mut self, puzzle: &Puzzle, input: &str, part: Option<u8>, timeout: Option<Duration>) -> Record {
+        let part1 = self.solve_part(puzzle.day, part, 1, timeout);
+        let part2 = self.solve_part(puzzle.day, part, 2, timeout);
+
+        Record {
+            day: puzzle.day,
+            name: puzzle.title,
+            input: input.to_string(),
+            parse_time: self.time,
+            part1: part1.answer,
+            time1: part1.time,
+            status1: part1.outcome,
+            part2: part2.answer,
+            time2: part2.time,
+            status2: part2.outcome,
+            cpu_time: part1.cpu_time + part2.cpu_time,
+            memory: [self.memory, part1.memory, part2.memory],
+        }
+    }
+
+    fn solve_part(&mut self, day: u32, selected: Option<u8>, part: u8, timeout: Option<Duration>) -> guard::Part {
+        if !parts(selected).any(|p| p == part) {
+            return guard::Part::skipped(guard::Outcome::Ok);
+        }
+        // A part which panicked or timed out took the day with it, but the parts don't share state
+        let event = match self.event.take() {
+            Some(event) => event,
+            None => match self.rebuild() {
+                Ok(event) => event,
+                Err(err) => return guard::Part::skipped(guard::Outcome::Panic(format!("{:#}", err))),
+            },
+        };
+        let (event, solved) = guard::run(event, day, part, timeout);
+        self.event = event;
+        solved
+    }
+
+    fn rebuild(&self) -> anyhow::Result<Box<dyn Advent>> {
+        let mut event = (self.constructor)(self.content)?;
+        if let Some(params) = self.params {
+            params.apply(event.as_mut())?;
+        }
+        Ok(event)
+    }
+}
+
+/// Parts picked with `--part`, both when it's missing.
+pub fn parts(selected: Option<u8>) -> impl Iterator<Item = u8> {
+    (1..=2).filter(move |&part| selected.is_none_or(|s| s == part))
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use anyhow::Context;
+use advent_of_code::Visualize;
+
+/// Where the frames of `--visualize` go.
+pub enum Output {
+    /// Redraws the terminal, waiting this long between frames.
+    Terminal(Duration),
+    /// Writes each frame to its own text file.
+    Directory(PathBuf),
+}
+
+/// Shows every frame of one part, returning how many there were.
+pub fn play(visualization: &mut dyn Visualize, day: u32, part: u8, output: &Output) -> anyhow::Result<usize> {
+    if let Output::Directory(dir) = output {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+    }
+
+    let mut count = 0;
+    let mut result = Ok(());
+    visualization.visualize(part, &mut |frame| {
+        if result.is_err() {
+            return;
+        }
+        count += 1;
+        result = match output {
+            Output::Terminal(frame_time) => {
+                // Clear the screen and move the cursor back to the top
+                let mut stdout = io::stdout().lock();
+                let drawn = write!(stdout, "\x1b[2J\x1b[HDay {} part {}, frame {}\n\n{}\n", day, part, count, frame)
+                    .and_then(|_| stdout.flush());
+                thread::sleep(*frame_time);
+                drawn.context("Could not draw the frame")
+            }
+            Output::Directory(dir) => {
+                let path = dir.join(format!("day_{:02}_part{}_{:04}.txt", day, part, count));
+                fs::write(&path, frame).with_context(|| format!("Could not write {}", path.display()))
+            }
+        };
+    });
+    result.map(|_| count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter;
+
+    impl Visualize for Counter {
+        fn visualize(&mut self, part: u8, frame: &mut dyn FnMut(String)) {
+            (1..=3).for_each(|i| frame(format!("part {} step {}", part, i)));
+        }
+    }
+
+    #[test]
+    fn writes_frames_to_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc_frames_{}", std::process::id()));
+        let frames = play(&mut Counter, 7, 2, &Output::Directory(dir.clone())).unwrap();
+        assert_eq!(frames, 3);
+        assert_eq!(fs::read_to_string(dir.join("day_07_part2_0003.txt")).unwrap(), "part 2 step 3");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
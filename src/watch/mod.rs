@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use humantime::format_duration;
+use crate::history::change;
+use crate::report::Record;
+
+/// How often `--watch` looks at the files.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+// Missing files count as a state of their own, so creating one is a change too
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Files polled for a day: its input and example, the selected file when it is a named example,
+/// and the one given with `--input`.
+pub fn day_files(day_dir: &Path, main_file: &str, input: Option<&Path>) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = vec![];
+    let named = ["input", "example", main_file].map(|name| day_dir.join(format!("{}.txt", name)));
+    for path in named.into_iter().chain(input.map(Path::to_path_buf)) {
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Files read by the selected days, polled for changes.
+pub struct Watcher {
+    files: Vec<(u32, PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new(files: Vec<(u32, PathBuf)>) -> Self {
+        let files = files
+            .into_iter()
+            .map(|(day, path)| {
+                let stamp = stamp(&path);
+                (day, path, stamp)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Days with a file which changed since the last call.
+    pub fn changed(&mut self) -> Vec<u32> {
+        let mut days = vec![];
+        for (day, path, last) in &mut self.files {
+            let now = stamp(path);
+            if now != *last {
+                *last = now;
+                if !days.contains(day) {
+                    days.push(*day);
+                }
+            }
+        }
+        days
+    }
+
+    /// Waits until a file changed, returning the days to run again.
+    pub fn wait(&mut self) -> Vec<u32> {
+        loop {
+            let days = self.changed();
+            if !days.is_empty() {
+                return days;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Describes what changed since the previous run of the same day.
+pub fn diff(previous: &Record, current: &Record, parts: impl Iterator<Item = u8>) -> Vec<String> {
+    let result = |record: &Record, part: u8| match record.status(part) {
+        status if status.is_ok() => record.answer(part).to_string(),
+        status => status.to_string(),
+    };
+
+    let mut lines = vec![];
+    for part in parts {
+        let (before, now) = (result(previous, part), result(current, part));
+        if before == now {
+            lines.push(format!("Part {}: same answer", part));
+        } else if before.contains('\n') || now.contains('\n') {
+            lines.push(format!("Part {}: answer changed", part));
+        } else {
+            lines.push(format!("Part {}: {} (was {})", part, now, before));
+        }
+    }
+    lines.push(format!(
+        "Total time: {} (was {}, {:+.1}%)",
+        format_duration(current.total()),
+        format_duration(previous.total()),
+        change(previous.total(), current.total())
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code::answer::Answer;
+    use crate::guard::Outcome;
+
+    fn record(part1: Answer, status2: Outcome, millis: u64) -> Record {
+        Record {
+            day: 6,
+            name: "Lanternfish",
+            input: "example".to_string(),
+            parse_time: Duration::ZERO,
+            part1,
+            time1: Duration::from_millis(millis),
+            status1: Outcome::Ok,
+            part2: Answer::Empty,
+            time2: Duration::ZERO,
+            status2,
+            cpu_time: Duration::ZERO,
+            memory: [None; 3],
+        }
+    }
+
+    #[test]
+    fn notices_changed_files() {
+        let dir = std::env::temp_dir().join(format!("aoc_watch_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (example, input) = (dir.join("example.txt"), dir.join("input.txt"));
+        fs::write(&example, "3,4,3,1,2").unwrap();
+
+        let mut watcher = Watcher::new(vec![(6, example.clone()), (7, input.clone())]);
+        assert_eq!(watcher.changed(), Vec::<u32>::new());
+        fs::write(&example, "3,4,3,1,2,5").unwrap();
+        fs::write(&input, "16,1,2").unwrap();
+        assert_eq!(watcher.changed(), vec![6, 7]);
+        assert_eq!(watcher.changed(), Vec::<u32>::new());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn notices_the_file_which_isnt_solved() {
+        let dir = std::env::temp_dir().join(format!("aoc_watch_day_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input.txt"), "16,1,2").unwrap();
+        fs::write(dir.join("example.txt"), "16,1,2,0").unwrap();
+
+        let files = day_files(&dir, "input", None);
+        assert_eq!(files, [dir.join("input.txt"), dir.join("example.txt")]);
+        let mut watcher = Watcher::new(files.into_iter().map(|path| (7, path)).collect());
+        fs::write(dir.join("example.txt"), "16,1,2,0,4").unwrap();
+        assert_eq!(watcher.changed(), vec![7]);
+
+        let other = dir.join("other.txt");
+        assert_eq!(day_files(&dir, "examples/small", Some(&other))[2..], [dir.join("examples/small.txt"), other]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn describes_changes() {
+        let before = record(Answer::Int(26), Outcome::Ok, 10);
+        let after = record(Answer::Int(5934), Outcome::Timeout, 15);
+        assert_eq!(
+            diff(&before, &after, 1..=2),
+            ["Part 1: 5934 (was 26)", "Part 2: TIMEOUT (was -)", "Total time: 15ms (was 10ms, +50.0%)"]
+        );
+        assert_eq!(diff(&before, &before, 1..=1)[0], "Part 1: same answer");
+    }
+}